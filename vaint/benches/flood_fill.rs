@@ -0,0 +1,81 @@
+//! Comparación del relleno por filas ([`vaint::algorithms::flood_fill`]) contra su implementación
+//! anterior, que ordenaba el contorno y el buffer completo en cada fila.
+//!
+//! ```bash
+//! cargo bench --bench flood_fill
+//! ```
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use vaint::PixelCoord;
+use vaint::algorithms::{flood_fill, write_circle_middle_point};
+
+/// Radios evaluados.
+const RADII: [i32; 3] = [50, 500, 2000];
+/// Radio máximo evaluado con la implementación anterior. Con un radio de 500 cada iteración ya
+/// tarda decenas de segundos, y con 2000 tardaría horas, por lo que se omite.
+const LEGACY_MAX_RADIUS: i32 = 500;
+
+/// Implementación anterior de `flood_fill`: filtra el contorno completo por cada punto, y ordena el
+/// buffer completo tras cada fila.
+fn legacy_flood_fill(outline_points: &[PixelCoord], buf: &mut Vec<PixelCoord>) {
+    use itertools::Itertools;
+
+    if outline_points.is_empty() {
+        return;
+    }
+
+    let copied_outline = outline_points.iter().sorted_by_key(|(_, y)| *y).collect::<Vec<_>>();
+    let bottom_y = copied_outline.last().unwrap().1;
+    for &(_, y) in copied_outline.iter().copied() {
+        if y > bottom_y {
+            break;
+        }
+        let Some((min, max)) =
+            copied_outline.iter().filter(|(_, current_y)| *current_y == y).map(|(x, _)| *x).minmax().into_option()
+        else {
+            continue;
+        };
+
+        buf.extend((min + 1..max).map(|x| (x, y)));
+        buf.sort_unstable();
+        buf.dedup();
+    }
+}
+
+fn circle_outline(radius: i32) -> Vec<PixelCoord> {
+    let mut outline = Vec::new();
+    write_circle_middle_point((radius, radius), radius, &mut outline);
+    outline
+}
+
+fn bench_flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flood_fill");
+    group.sample_size(10);
+
+    for radius in RADII {
+        let outline = circle_outline(radius);
+
+        group.bench_with_input(BenchmarkId::new("span_buckets", radius), &outline, |b, outline| {
+            b.iter(|| {
+                let mut buf = Vec::new();
+                flood_fill(black_box(outline), &mut buf);
+                buf
+            })
+        });
+
+        if radius <= LEGACY_MAX_RADIUS {
+            group.bench_with_input(BenchmarkId::new("legacy_sort_per_row", radius), &outline, |b, outline| {
+                b.iter(|| {
+                    let mut buf = Vec::new();
+                    legacy_flood_fill(black_box(outline), &mut buf);
+                    buf
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_flood_fill);
+criterion_main!(benches);
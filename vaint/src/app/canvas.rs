@@ -0,0 +1,137 @@
+//! Lienzo de egui que dibuja las figuras dentro de la ventana del panel de control.
+//!
+//! eframe y glium no pueden compartir el `EventLoop` de winit, así que el lienzo no usa OpenGL: las
+//! figuras se renderizan por software con [`crate::raster`] y la imagen se muestra como una textura
+//! de egui. Solo se renderiza de nuevo cuando cambian las figuras o la vista.
+use eframe::egui::{self, ColorImage, Pos2, Rect, Sense, TextureHandle, TextureOptions};
+
+use crate::algorithms::ClipRect;
+use crate::view::{Camera, shapes_bounds};
+use crate::{Color, GlShapeList, raster};
+
+/// Lienzo con las figuras, que se puede desplazar arrastrando y acercar con la rueda del ratón. Un
+/// doble clic ajusta la vista a todas las figuras.
+pub struct Canvas {
+    shapes_list: GlShapeList,
+    background_color: Color,
+    /// Vista de la escena. Cada punto de egui se toma como un pixel de la ventana.
+    pub camera: Camera,
+    texture: Option<TextureHandle>,
+    /// Región de la escena de la imagen en la textura, o `None` si hay que renderizarla de nuevo.
+    rendered: Option<ClipRect>,
+}
+
+impl Canvas {
+    /// Espacio libre alrededor de las figuras al ajustar la vista, en puntos.
+    const FIT_MARGIN: f32 = 20.0;
+    /// Puntos de desplazamiento de egui por cada paso de la rueda del ratón.
+    const POINTS_PER_STEP: f32 = 50.0;
+    /// Factor de acercamiento por cada paso de la rueda del ratón.
+    const ZOOM_STEP: f32 = 1.1;
+
+    /// Crea un lienzo con las figuras y el color de fondo dados.
+    pub fn new(shapes_list: GlShapeList, background_color: Color) -> Self {
+        Self { shapes_list, background_color, camera: Camera::default(), texture: None, rendered: None }
+    }
+
+    /// Figuras del lienzo.
+    pub fn shapes(&self) -> &GlShapeList { &self.shapes_list }
+
+    /// Reemplaza las figuras y el color de fondo del lienzo.
+    pub fn set_shapes(&mut self, shapes_list: GlShapeList, background_color: Color) {
+        self.shapes_list = shapes_list;
+        self.background_color = background_color;
+        self.rendered = None;
+    }
+
+    /// Ajusta la vista para que se vean todas las figuras en un lienzo de las dimensiones dadas.
+    pub fn fit_all_shapes(&mut self, dimensions: (u32, u32)) {
+        if let Some(bounds) = shapes_bounds(&self.shapes_list) {
+            self.camera.fit(&bounds, dimensions, Self::FIT_MARGIN);
+        }
+    }
+
+    /// Dibuja el lienzo ocupando todo el espacio disponible de `ui`, y atiende el ratón sobre él.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        let dimensions = (rect.width() as u32, rect.height() as u32);
+        self.handle_input(ui, &response, rect.min, dimensions);
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, self.background_color);
+        let region = self.camera.visible_rect(dimensions);
+        if dimensions.0 == 0 || dimensions.1 == 0 || region.x_max < region.x_min || region.y_max < region.y_min {
+            return response;
+        }
+
+        let texture = self.texture(ui.ctx(), &region);
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        painter.image(texture.id(), image_rect(&self.camera, rect.min, &region), uv, egui::Color32::WHITE);
+        response
+    }
+
+    /// Desplaza o acerca la vista según el ratón.
+    fn handle_input(&mut self, ui: &egui::Ui, response: &egui::Response, origin: Pos2, dimensions: (u32, u32)) {
+        if response.double_clicked() {
+            self.fit_all_shapes(dimensions);
+        } else if response.dragged() {
+            let delta = response.drag_delta();
+            self.camera.pan((delta.x, delta.y));
+        }
+
+        let Some(cursor) = response.hover_pos() else { return };
+        let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            let anchor = cursor - origin;
+            self.camera.zoom_at((anchor.x, anchor.y), Self::ZOOM_STEP.powf(scroll / Self::POINTS_PER_STEP));
+        }
+    }
+
+    /// Textura con la región dada de la escena, renderizada de nuevo solo si cambió.
+    fn texture(&mut self, ctx: &egui::Context, region: &ClipRect) -> &TextureHandle {
+        if self.rendered != Some(*region) || self.texture.is_none() {
+            let image = raster::render_region(&self.shapes_list, self.background_color, region);
+            let image = ColorImage::from_rgb([image.width() as usize, image.height() as usize], image.as_raw());
+            // Al acercar la vista, cada pixel de la escena se ve como un cuadrado
+            match &mut self.texture {
+                Some(texture) => texture.set(image, TextureOptions::NEAREST),
+                None => self.texture = Some(ctx.load_texture("vaint-canvas", image, TextureOptions::NEAREST)),
+            }
+            self.rendered = Some(*region);
+        }
+        self.texture.as_ref().expect("La textura se acaba de crear")
+    }
+}
+
+/// Rectángulo de la pantalla en el que se ve la región dada de la escena, en un lienzo con la
+/// esquina superior izquierda en `origin`.
+fn image_rect(camera: &Camera, origin: Pos2, region: &ClipRect) -> Rect {
+    let (x_min, y_min) = camera.world_to_screen((region.x_min as f32, region.y_min as f32));
+    let (x_max, y_max) = camera.world_to_screen(((region.x_max + 1) as f32, (region.y_max + 1) as f32));
+    Rect::from_min_max(Pos2::new(x_min, y_min), Pos2::new(x_max, y_max)).translate(origin.to_vec2())
+}
+
+#[cfg(test)]
+mod test {
+    use eframe::egui::{Pos2, Rect};
+
+    use super::image_rect;
+    use crate::algorithms::ClipRect;
+    use crate::view::Camera;
+
+    #[test]
+    fn image_covers_region_pixels() {
+        let origin = Pos2::new(100.0, 50.0);
+        let region = ClipRect::new((0, 0), (9, 4));
+        assert_eq!(
+            image_rect(&Camera::default(), origin, &region),
+            Rect::from_min_max(Pos2::new(100.0, 50.0), Pos2::new(110.0, 55.0))
+        );
+
+        // La región visible empieza en el pixel que contiene al desplazamiento de la cámara
+        let camera = Camera { offset: (2.5, 1.0), zoom: 4.0 };
+        let region = camera.visible_rect((40, 20));
+        assert_eq!(region, ClipRect::new((2, 1), (12, 5)));
+        assert_eq!(image_rect(&camera, origin, &region), Rect::from_min_max(Pos2::new(98.0, 50.0), Pos2::new(142.0, 70.0)));
+    }
+}
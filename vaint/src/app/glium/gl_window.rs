@@ -1,10 +1,17 @@
 use glium::index::{NoIndices, PrimitiveType};
 use glium::winit::application::ApplicationHandler;
-use glium::{Display, DrawParameters, Surface, uniform};
+use glium::winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
+use glium::winit::keyboard::{Key, ModifiersState, NamedKey};
+use glium::{Blend, Display, DrawParameters, Surface, uniform};
 use glutin::surface::WindowSurface;
+use image::ImageFormat;
 
-use super::GlShapeList;
-use crate::algorithms::flood_fill;
+use super::selection::{Handle, Selection, SelectionDrag, selection_overlay};
+use super::{Camera, Command, GlShapeList, History, Placement, Tool};
+use crate::algorithms::{ClipRect, write_boundary_fill, write_seed_fill};
+use crate::ipc::Message;
+use crate::raster::{ShapePoints, generate_shapes};
+use crate::scene::SceneShape;
 use crate::{Color, PixelCoord, Shape, ShapeObject, ShapeStyle, Vertex};
 
 pub struct GlWindow {
@@ -14,12 +21,49 @@ pub struct GlWindow {
     /// Lista de Objetos a dibujar
     pub shapes_list: GlShapeList,
     pub background_color: Color,
+    /// Estado de las teclas modificadoras (Ctrl, Shift, ...).
+    pub modifiers: ModifiersState,
+    /// Herramienta activa.
+    pub tool: Tool,
+    /// Estilo utilizado por las herramientas de edición.
+    pub tool_style: ShapeStyle,
+    /// Última posición conocida del cursor, en pixeles de la ventana.
+    pub cursor_position: PixelCoord,
+    /// Vista de la escena. Las figuras están en coordenadas de la escena, y el cursor en
+    /// coordenadas de la ventana (ver [`GlWindow::cursor_world_position`]).
+    pub camera: Camera,
+    /// Indica si se está desplazando la vista arrastrando con el botón central del ratón.
+    pub panning: bool,
+    /// Pixel de la escena donde empezó el arrastre de una herramienta de figuras, si se está
+    /// arrastrando.
+    pub drag_start: Option<PixelCoord>,
+    /// Figura seleccionada con la herramienta de puntero.
+    pub selection: Option<Selection>,
+    /// Historial de cambios de [`GlWindow::shapes_list`], para deshacerlos con `Ctrl + Z` y
+    /// rehacerlos con `Ctrl + Y`.
+    pub history: History,
+    /// Formato de las imágenes guardadas con `Ctrl + S`. Se cambia con `Ctrl + Shift + S` entre
+    /// los de [`GlWindow::SCREENSHOT_FORMATS`].
+    pub screenshot_format: ImageFormat,
 }
 
 impl GlWindow {
+    /// Pixeles de la ventana que se dejan libres alrededor de las figuras al ajustar la vista.
+    const FIT_MARGIN: f32 = 20.0;
+    /// Lado de las asas de la caja de selección, en pixeles de la ventana.
+    const HANDLE_SIZE: f32 = 7.0;
+    /// Distancia máxima, en pixeles de la ventana, entre el cursor y el borde de una figura para
+    /// seleccionarla.
+    const HIT_TOLERANCE: f32 = 3.0;
+    /// Formatos en los que se pueden guardar las imágenes de la escena.
+    pub const SCREENSHOT_FORMATS: [ImageFormat; 3] = [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::Bmp];
     pub const WINDOW_TITLE: &'static str = "Vaint - OpenGL Windows";
+    /// Factor de acercamiento por cada paso de la rueda del ratón.
+    const ZOOM_STEP: f32 = 1.1;
 
     fn draw_frame(&mut self) {
+        let preview = self.drag_preview();
+        let overlay = self.selection_overlay();
         let display = &mut self.display;
         let program = self.program.get_or_insert_with(|| programa(display));
 
@@ -31,76 +75,422 @@ impl GlWindow {
         let (screen_width, screen_height) = target.get_dimensions();
         let uniforms = uniform! {
             screen_dimensions: [screen_width, screen_height],
-            matrix : [
-                [1.0, 0.0, 0.0, 0.0],
-                [0.0, 1.0, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [0.0, 0.0, 0.0, 1.0f32],
-            ],
+            matrix: self.camera.matrix(),
         };
+        // Con acercamiento, cada pixel de la escena cubre varios pixeles de la ventana
+        let point_size = Some(self.camera.zoom.max(1.0));
 
-        let outline_points = Self::generate_shapes(&self.shapes_list, (screen_width as i32, screen_height as i32));
+        let visible = self.camera.visible_rect((screen_width, screen_height));
+        let mut shape_points = generate_shapes(&self.shapes_list, &visible);
+        // La figura que se está arrastrando se dibuja encima de las demás
+        if let Some(preview) = preview {
+            shape_points.extend(generate_shapes(std::slice::from_ref(&preview), &visible));
+        }
+        shape_points.extend(generate_shapes(&overlay, &visible));
 
         // Dibujar el relleno de las figuras
-        for (outlines, style) in outline_points.iter().filter(|(_, s)| s.fill_color.is_some()) {
-            let Some(color) = style.fill_color else { unreachable!("El color de relleno es Some(_)") };
-
-            let mut fill_points = Vec::new();
-            flood_fill(outlines, &mut fill_points);
+        for ShapePoints { fill, style, .. } in shape_points.iter() {
+            let Some(color) = style.fill_color else { continue };
 
-            if fill_points.is_empty() {
+            if fill.is_empty() {
                 tracing::warn!("No hay puntos para rellenar la figura");
                 continue;
             }
 
-            let drawing_params = DrawParameters { ..Default::default() };
-            let fill_points =
-                fill_points.iter().map(|(x, y)| Vertex { position: [*x, *y], color: color.to_vec() }).collect::<Vec<_>>();
+            let drawing_params = DrawParameters { point_size, ..Default::default() };
+            let fill_points = fill.iter().map(|&(x, y)| Vertex::new([x, y], color)).collect::<Vec<_>>();
             let vertex_buffer = glium::VertexBuffer::new(display, &fill_points).unwrap();
             target.draw(&vertex_buffer, NoIndices(PrimitiveType::Points), program, &uniforms, &drawing_params).unwrap();
         }
 
         // Dibujar el contorno de las figuras
-        for (vertices, style) in outline_points.iter() {
-            let drawing_params = DrawParameters { point_size: Some(style.stroke_width), ..Default::default() };
-            let vertices = vertices
-                .iter()
-                .map(|(x, y)| Vertex { position: [*x, *y], color: style.stroke_color.unwrap_or(Color::BLACK).to_vec() })
-                .collect::<Vec<_>>();
+        for ShapePoints { outline, style, .. } in shape_points.iter() {
+            let Some(color) = style.stroke_color else { continue };
+            // La cobertura de cada punto se mezcla con lo ya dibujado
+            let drawing_params = DrawParameters { blend: Blend::alpha_blending(), point_size, ..Default::default() };
+            let vertices =
+                outline.iter().map(|&((x, y), coverage)| Vertex::new([x, y], color).coverage(coverage)).collect::<Vec<_>>();
             let vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
             target.draw(&vertex_buffer, NoIndices(PrimitiveType::Points), program, &uniforms, &drawing_params).unwrap();
         }
         target.finish().unwrap();
     }
 
-    /// Genera los puntos con color ([`Vertex`]) de cada figura a dibujar, y devuelve el grosor de
-    /// cada figura.
-    fn generate_shapes(shapes_list: &[ShapeObject], screen_dimensions: PixelCoord) -> Vec<(Vec<PixelCoord>, ShapeStyle)> {
-        let (screen_width, screen_height) = screen_dimensions;
-        // Filtra los puntos que están fuera de la pantalla.
-        let filter_inbounds = |(x, y): &PixelCoord| -> bool { (0..screen_width).contains(x) && (0..screen_height).contains(y) };
-        let mut vertices: Vec<(Vec<PixelCoord>, ShapeStyle)> = Vec::with_capacity(shapes_list.len());
+    /// Guarda la escena actual como una imagen en el directorio de trabajo, con el formato
+    /// [`GlWindow::screenshot_format`].
+    ///
+    /// La imagen se genera con [`crate::raster`], usando las dimensiones actuales de la ventana y
+    /// la vista de [`GlWindow::camera`], por lo que se guarda lo mismo que se ve en la ventana.
+    pub fn save_screenshot(&self) {
+        let path = screenshot_path(self.screenshot_format);
+        let dimensions = self.display.get_framebuffer_dimensions();
+
+        match crate::raster::export(&self.shapes_list, self.background_color, &self.camera, dimensions, &path) {
+            Ok(()) => tracing::info!("Imagen guardada en `{path}`"),
+            Err(e) => tracing::error!("No se pudo guardar la imagen `{path}`: {e}"),
+        }
+    }
+
+    /// Cambia [`GlWindow::screenshot_format`] al siguiente de [`GlWindow::SCREENSHOT_FORMATS`].
+    pub fn next_screenshot_format(&mut self) {
+        let formats = Self::SCREENSHOT_FORMATS;
+        let current = formats.iter().position(|&format| format == self.screenshot_format).unwrap_or(formats.len() - 1);
+        self.screenshot_format = formats[(current + 1) % formats.len()];
+        tracing::info!("Las imágenes se guardarán como {:?}", self.screenshot_format);
+    }
+
+    /// Rellena la región bajo el cursor con el color de relleno de [`GlWindow::tool_style`], y la
+    /// agrega a la lista de figuras.
+    ///
+    /// Con `Shift` presionado, la región se delimita por el color de borde de
+    /// [`GlWindow::tool_style`] en lugar del color bajo el cursor.
+    pub fn bucket_fill(&mut self) {
+        let Some(fill_color) = self.tool_style.fill_color else {
+            tracing::warn!("La cubeta no tiene un color de relleno definido");
+            return;
+        };
+        // El relleno se limita a la parte visible de la escena
+        let visible = self.camera.visible_rect(self.display.get_framebuffer_dimensions());
+        let image = crate::raster::render_region(&self.shapes_list, self.background_color, &visible);
+        let (x, y) = self.cursor_world_position();
+        let seed = (x - visible.x_min, y - visible.y_min);
+
+        let mut region = Vec::new();
+        match self.tool_style.stroke_color {
+            Some(boundary) if self.modifiers.shift_key() => {
+                write_boundary_fill(&image, seed, boundary, Tool::BUCKET_CONNECTIVITY, &mut region)
+            }
+            _ => write_seed_fill(&image, seed, Tool::BUCKET_CONNECTIVITY, &mut region),
+        }
+
+        if region.is_empty() {
+            return;
+        }
+        for (x, y) in region.iter_mut() {
+            *x += visible.x_min;
+            *y += visible.y_min;
+        }
+        tracing::debug!("Cubeta: {} pixeles rellenados", region.len());
+        self.execute(Command::add(self.shapes_list.len(), ShapeObject::new_region(&region, fill_color)));
+    }
+
+    /// Pixel de la escena bajo el cursor.
+    pub fn cursor_world_position(&self) -> PixelCoord { self.camera.screen_to_world_pixel(self.cursor_position) }
+
+    /// Figura que resultaría de soltar el botón del ratón en la posición actual del cursor, si se
+    /// está arrastrando una herramienta de figuras.
+    pub fn drag_preview(&self) -> Option<ShapeObject> {
+        let mut shape = self.tool.shape_from_drag(self.drag_start?, self.cursor_world_position())?;
+        *shape.style_mut() = self.tool_style;
+        Some(shape)
+    }
+
+    /// Termina el arrastre de la herramienta de figuras, agregando la figura resultante a la lista.
+    fn finish_drag(&mut self) {
+        let Some(shape) = self.drag_preview() else {
+            self.drag_start = None;
+            return;
+        };
+        tracing::debug!("{} creado en {:?}", shape.shape_name(), shape.center);
+        self.drag_start = None;
+        self.execute(Command::add(self.shapes_list.len(), shape));
+    }
+
+    /// Termina la edición de la figura seleccionada, guardando el arrastre completo en el historial
+    /// como un solo comando.
+    fn finish_selection_drag(&mut self) {
+        let Some(selection @ Selection { index, drag: Some(drag), .. }) = self.selection else { return };
+        self.selection = Some(Selection { drag: None, ..selection });
+
+        let Some(shape) = self.shapes_list.get(index) else { return };
+        let (from, to) = (drag.origin(shape), Placement::of(shape));
+        if from != to {
+            self.history.record(Command::Move { index, from, to });
+        }
+    }
+
+    /// Aplica el comando dado a la lista de figuras, guardándolo en el historial.
+    pub fn execute(&mut self, command: Command) {
+        self.history.execute(command, &mut self.shapes_list);
+        self.window.request_redraw();
+    }
+
+    /// Deshace el último cambio de la lista de figuras.
+    pub fn undo(&mut self) {
+        self.finish_selection_drag();
+        match self.history.undo(&mut self.shapes_list) {
+            true => self.after_history_change(),
+            false => tracing::debug!("No hay cambios que deshacer"),
+        }
+    }
+
+    /// Rehace el último cambio deshecho de la lista de figuras.
+    pub fn redo(&mut self) {
+        self.finish_selection_drag();
+        match self.history.redo(&mut self.shapes_list) {
+            true => self.after_history_change(),
+            false => tracing::debug!("No hay cambios que rehacer"),
+        }
+    }
+
+    /// Quita la selección, ya que el índice de la figura seleccionada puede haber cambiado, y
+    /// redibuja la ventana.
+    fn after_history_change(&mut self) {
+        self.selection = None;
+        self.window.request_redraw();
+    }
+
+    /// Elimina la figura seleccionada.
+    pub fn delete_selected(&mut self) {
+        let Some(Selection { index, .. }) = self.selection.take() else { return };
+        self.execute(Command::delete(index));
+    }
+
+    /// Aplica el estilo de las herramientas, [`GlWindow::tool_style`], a la figura seleccionada.
+    pub fn restyle_selected(&mut self) {
+        let Some(Selection { index, .. }) = self.selection else { return };
+        let command = Command::restyle(index, &self.shapes_list[index], self.tool_style);
+        self.execute(command);
+        // El grosor del borde cambia la caja delimitadora
+        self.selection = Some(Selection::new(index, &self.shapes_list[index]));
+    }
+
+    /// Sube (`forward`) o baja la figura seleccionada una posición en el orden de dibujo.
+    pub fn reorder_selected(&mut self, forward: bool) {
+        let Some(selection @ Selection { index, .. }) = self.selection else { return };
+        let to = match forward {
+            true if index + 1 < self.shapes_list.len() => index + 1,
+            false if index > 0 => index - 1,
+            _ => return,
+        };
+        self.execute(Command::Reorder { from: index, to });
+        self.selection = Some(Selection { index: to, drag: None, ..selection });
+    }
+
+    /// Índice de la figura más arriba bajo el pixel dado de la escena, ya sea sobre su borde o
+    /// dentro de su relleno.
+    pub fn shape_at(&self, point: PixelCoord) -> Option<usize> {
+        let tolerance = Self::HIT_TOLERANCE / self.camera.zoom;
+        self.shapes_list.iter().rposition(|shape| shape.hit_outline(point, tolerance) || shape.contains_point(point))
+    }
+
+    /// Lado de las asas de la caja de selección en pixeles de la escena, para que se vean del
+    /// mismo tamaño con cualquier acercamiento.
+    fn handle_size(&self) -> u32 { ((Self::HANDLE_SIZE / self.camera.zoom).round() as u32).max(1) }
+
+    /// Asa de la caja de selección bajo el pixel dado de la escena, junto con la caja.
+    fn handle_at(&self, point: PixelCoord) -> Option<(Handle, ClipRect)> {
+        let bounds = self.selection?.bounds?;
+        let handle = Handle::ALL.into_iter().find(|handle| handle.contains(&bounds, self.handle_size(), point))?;
+        Some((handle, bounds))
+    }
+
+    /// Figuras que dibujan la caja de selección, con sus asas, alrededor de la figura seleccionada.
+    fn selection_overlay(&self) -> Vec<ShapeObject> {
+        let bounds = self.selection.and_then(|selection| selection.bounds);
+        bounds.map(|bounds| selection_overlay(&bounds, self.handle_size())).unwrap_or_default()
+    }
 
-        for shape in shapes_list
-            .iter()
-            // Incluir solo figuras con color de borde definido.
-            .filter(|obj| obj.style().stroke_color.is_some())
-        {
-            let mut points: Vec<PixelCoord> = Vec::new();
-            shape.write_outline_points(&mut points);
-            let style = shape.style();
-            let iter_vertices = points.into_iter().filter(filter_inbounds).collect();
-            vertices.push((iter_vertices, *style));
+    /// Empieza a editar con la herramienta de puntero: arrastrar un asa de la figura seleccionada
+    /// la redimensiona, y arrastrar una figura la selecciona y la mueve. Un clic fuera de las
+    /// figuras quita la selección.
+    fn start_selection_drag(&mut self) {
+        let cursor = self.cursor_world_position();
+        self.selection = match self.handle_at(cursor) {
+            Some((handle, bounds)) => {
+                self.selection.map(|selection| {
+                    let ShapeObject { center, transform, .. } = self.shapes_list[selection.index];
+                    Selection { drag: Some(SelectionDrag::Resize { handle, bounds, center, transform }), ..selection }
+                })
+            }
+            None => {
+                self.shape_at(cursor).map(|index| {
+                    let shape = &self.shapes_list[index];
+                    let drag = SelectionDrag::Move { start: cursor, center: shape.center };
+                    Selection { drag: Some(drag), ..Selection::new(index, shape) }
+                })
+            }
+        };
+        match self.selection {
+            Some(Selection { index, .. }) => tracing::debug!("{} seleccionado", self.shapes_list[index].shape_name()),
+            None => tracing::trace!("Ninguna figura seleccionada"),
         }
-        vertices
+        self.window.request_redraw();
     }
 
-    fn handle_window_event(&mut self, _event: &glium::winit::event::WindowEvent) {
-        let _window = &self.window;
-        tracing::debug!("Do nothing....");
+    /// Aplica un mensaje del panel de control (ver [`crate::ipc`]), o una escena recargada del
+    /// disco.
+    ///
+    /// Los cambios del panel no se pueden deshacer en la ventana. Eliminar una figura o cargar una
+    /// escena vacía el historial, ya que cambia las posiciones de las figuras en la lista; agregar
+    /// o reemplazar una figura las conserva.
+    pub fn handle_message(&mut self, message: Message) {
+        tracing::debug!("Mensaje recibido: {message:?}");
+        let position = |shapes: &GlShapeList, id| shapes.iter().position(|shape: &ShapeObject| shape.id == Some(id));
+        let build = |id, shape: &SceneShape| shape.to_shape_object().id(id);
+
+        match message {
+            Message::LoadScene { scene } => {
+                let drawn = self.shapes_list.iter().filter(|shape| shape.id.is_none()).count();
+                if drawn > 0 {
+                    tracing::warn!("Se descartan {drawn} figuras dibujadas en la ventana al cargar la escena");
+                }
+                self.shapes_list = (0..).zip(&scene.shapes).map(|(id, shape)| build(id, shape)).collect();
+                self.background_color = scene.background_color;
+                let (width, height) = scene.canvas_size;
+                let _ = self.window.request_inner_size(glium::winit::dpi::PhysicalSize::new(width, height));
+                self.selection = None;
+                self.history.clear();
+            }
+            Message::AddShape { id, shape } => self.shapes_list.push(build(id, &shape)),
+            Message::UpdateShape { id, shape } => {
+                match position(&self.shapes_list, id) {
+                    Some(index) => {
+                        self.shapes_list[index] = build(id, &shape);
+                        if let Some(selection) = self.selection.as_mut().filter(|selection| selection.index == index) {
+                            selection.bounds = self.shapes_list[index].bounding_box();
+                        }
+                    }
+                    None => {
+                        tracing::warn!("No hay una figura con id {id} para actualizar; se agrega al final");
+                        self.shapes_list.push(build(id, &shape));
+                    }
+                }
+            }
+            Message::RemoveShape { id } => {
+                if let Some(index) = position(&self.shapes_list, id) {
+                    self.shapes_list.remove(index);
+                    self.selection = None;
+                    self.history.clear();
+                }
+            }
+            Message::SetBackground { color } => self.background_color = color,
+        }
+        self.drag_start = None;
+        self.window.request_redraw();
+    }
+
+    /// Ajusta la vista para que se vean todas las figuras.
+    pub fn fit_all_shapes(&mut self) {
+        let Some(bounds) = super::shapes_bounds(&self.shapes_list) else { return };
+        self.camera.fit(&bounds, self.display.get_framebuffer_dimensions(), Self::FIT_MARGIN);
+        tracing::debug!("Vista ajustada a {bounds:?}: {:?}", self.camera);
+        self.window.request_redraw();
+    }
+
+    /// Acerca o aleja la vista alrededor del cursor, según el movimiento de la rueda del ratón.
+    fn handle_mouse_wheel(&mut self, delta: &MouseScrollDelta) {
+        let steps = match delta {
+            MouseScrollDelta::LineDelta(_, y) => *y,
+            // Los touchpads reportan pixeles; se toman unos 50 pixeles por paso
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / 50.0,
+        };
+        let (x, y) = self.cursor_position;
+        self.camera.zoom_at((x as f32, y as f32), Self::ZOOM_STEP.powf(steps));
+        self.window.request_redraw();
+    }
+
+    /// Actualiza la posición del cursor, desplazando la vista si se está arrastrando con el botón
+    /// central del ratón, o editando la figura que se está arrastrando.
+    fn handle_cursor_moved(&mut self, position: PixelCoord) {
+        let (old_x, old_y) = std::mem::replace(&mut self.cursor_position, position);
+        if self.panning {
+            self.camera.pan(((position.0 - old_x) as f32, (position.1 - old_y) as f32));
+        }
+        let cursor = self.cursor_world_position();
+        if let Some(selection) = self.selection.as_mut().filter(|selection| selection.drag.is_some()) {
+            selection.drag_to(&mut self.shapes_list[selection.index], cursor);
+        }
+        // La vista previa de la figura y la selección siguen al cursor
+        if self.panning || self.drag_start.is_some() || self.selection.is_some_and(|selection| selection.drag.is_some()) {
+            self.window.request_redraw();
+        }
+    }
+
+    /// Maneja los atajos de teclado formados por un caracter.
+    fn handle_character_key(&mut self, key: &str) {
+        match (self.modifiers.control_key(), Tool::from_key(key)) {
+            // Ctrl + Shift + S: Cambiar el formato de las imágenes, Ctrl + S: Guardar la escena como imagen
+            (true, _) if key.eq_ignore_ascii_case("s") && self.modifiers.shift_key() => self.next_screenshot_format(),
+            (true, _) if key.eq_ignore_ascii_case("s") => self.save_screenshot(),
+            // Ctrl + Z: Deshacer, Ctrl + Y o Ctrl + Shift + Z: Rehacer
+            (true, _) if key.eq_ignore_ascii_case("z") && !self.modifiers.shift_key() => self.undo(),
+            (true, _) if key.eq_ignore_ascii_case("z") || key.eq_ignore_ascii_case("y") => self.redo(),
+            // S: Aplicar el estilo de las herramientas a la figura seleccionada
+            (false, _) if key.eq_ignore_ascii_case("s") => self.restyle_selected(),
+            // ] y [: Subir o bajar la figura seleccionada en el orden de dibujo
+            (false, _) if key == "]" => self.reorder_selected(true),
+            (false, _) if key == "[" => self.reorder_selected(false),
+            // F: Ajustar la vista a todas las figuras
+            (false, _) if key.eq_ignore_ascii_case("f") => self.fit_all_shapes(),
+            // Cambiar de herramienta
+            (false, Some(tool)) => {
+                self.tool = tool;
+                // Cambiar de herramienta cancela el arrastre en curso y la selección
+                self.drag_start = None;
+                self.selection = None;
+                self.window.request_redraw();
+                tracing::info!("Herramienta seleccionada: {tool:?}");
+            }
+            _ => tracing::trace!("Tecla sin atajo: {key:?}"),
+        }
+    }
+
+    fn handle_window_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => self.modifiers = modifiers.state(),
+            WindowEvent::CursorMoved { position, .. } => self.handle_cursor_moved((position.x as i32, position.y as i32)),
+            WindowEvent::MouseWheel { delta, .. } => self.handle_mouse_wheel(delta),
+            WindowEvent::MouseInput { state, button: MouseButton::Middle, .. } => {
+                self.panning = *state == ElementState::Pressed;
+            }
+            WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
+                match self.tool {
+                    Tool::Pointer => self.start_selection_drag(),
+                    Tool::Bucket => self.bucket_fill(),
+                    Tool::Rectangle | Tool::Circle | Tool::Ellipse | Tool::Line => {
+                        self.drag_start = Some(self.cursor_world_position())
+                    }
+                }
+            }
+            WindowEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left, .. } => {
+                if self.drag_start.is_some() {
+                    self.finish_drag();
+                }
+                self.finish_selection_drag();
+            }
+            WindowEvent::KeyboardInput {
+                event: KeyEvent { state: ElementState::Pressed, logical_key: Key::Character(c), repeat: false, .. },
+                ..
+            } => self.handle_character_key(c),
+            // Suprimir o Retroceso: Eliminar la figura seleccionada
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent { state: ElementState::Pressed, logical_key: Key::Named(NamedKey::Delete | NamedKey::Backspace), .. },
+                ..
+            } => self.delete_selected(),
+            _ => tracing::trace!("Evento no manejado: {event:?}"),
+        }
     }
 }
 
+/// Ruta de una imagen nueva en el directorio de trabajo, con la fecha actual en milisegundos.
+///
+/// Si ya existe una imagen con esa fecha, se agrega un contador al nombre para no reemplazarla.
+fn screenshot_path(format: ImageFormat) -> String {
+    let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let extension = format.extensions_str().first().copied().unwrap_or("png");
+    (0..)
+        .map(|n| {
+            match n {
+                0 => format!("vaint_{timestamp}.{extension}"),
+                n => format!("vaint_{timestamp}_{n}.{extension}"),
+            }
+        })
+        .find(|path| !std::path::Path::new(path).exists())
+        .expect("Hay infinitos nombres posibles")
+}
+
 fn programa(display: &Display<WindowSurface>) -> glium::Program {
     use glium::program;
     program!(display,
@@ -110,25 +500,32 @@ fn programa(display: &Display<WindowSurface>) -> glium::Program {
         
                     in vec2 position;  // Coordenadas XY representadas con Vec2
                     in vec3 color;     // RGB representado con Vec3 
+                    in float coverage; // Fracción del pixel cubierta por el punto
                     uniform uvec2 screen_dimensions;
+                    uniform mat4 matrix;  // Vista de la cámara: de la escena a pixeles de la ventana
         
                     out vec3 vColor;
+                    out float vCoverage;
         
                     void main() {
-                        vec2 flipped_position = vec2(position.x, screen_dimensions.y - position.y);
+                        // Se desplaza medio pixel para que cada punto quede en el centro de su pixel
+                        vec2 screen_position = (matrix * vec4(position + 0.5, 0.0, 1.0)).xy;
+                        vec2 flipped_position = vec2(screen_position.x, screen_dimensions.y - screen_position.y);
                         vec2 normalized_device_coords = (flipped_position / vec2(screen_dimensions)) * 2.0 - 1.0;
                         gl_Position = vec4(normalized_device_coords, 0.0, 1.0); // 2D → 4D clip space
                         vColor = color;
+                        vCoverage = coverage;
                     }
                 ",
 
             fragment: "
                     #version 330
                     in vec3 vColor; // RGB representado con Vec3 
+                    in float vCoverage;
                     out vec4 frag_color;
         
                     void main() {
-                        frag_color = vec4(vColor, 1.0); // Alpha = 1.0 (Sin transparencia)
+                        frag_color = vec4(vColor, vCoverage); // La cobertura se usa como transparencia
                     }
                 ",
         },
@@ -136,13 +533,19 @@ fn programa(display: &Display<WindowSurface>) -> glium::Program {
     .unwrap()
 }
 
-impl ApplicationHandler for GlWindow {
+impl ApplicationHandler<Message> for GlWindow {
     /// Emitted when the application has been resumed.
     fn resumed(&mut self, _event_loop: &glium::winit::event_loop::ActiveEventLoop) {
         // Hacer nada
         tracing::debug!("Application resumed!");
     }
 
+    /// Mensaje del panel de control, enviado por [`super::run_loop_ipc`], o escena recargada por
+    /// [`super::run_loop_standalone`].
+    fn user_event(&mut self, _event_loop: &glium::winit::event_loop::ActiveEventLoop, message: Message) {
+        self.handle_message(message);
+    }
+
     fn window_event(
         &mut self,
         event_loop: &glium::winit::event_loop::ActiveEventLoop,
@@ -0,0 +1,260 @@
+//! Historial de cambios de la lista de figuras, para deshacerlos y rehacerlos.
+use std::collections::VecDeque;
+
+use super::GlShapeList;
+use crate::geometries::Transform;
+use crate::{PixelCoord, Shape, ShapeObject, ShapeStyle};
+
+/// Posición y transformación de una figura, que cambian al moverla o redimensionarla.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub center: PixelCoord,
+    pub transform: Transform,
+}
+
+impl Placement {
+    /// Posición y transformación actuales de la figura dada.
+    pub fn of(shape: &ShapeObject) -> Self { Self { center: shape.center, transform: shape.transform } }
+
+    /// Lleva la figura dada a esta posición y transformación.
+    fn apply_to(&self, shape: &mut ShapeObject) {
+        shape.center = self.center;
+        shape.transform = self.transform;
+    }
+}
+
+/// Cambio de la lista de figuras que se puede deshacer.
+///
+/// Las figuras que no están en la lista (una figura agregada después de deshacer su comando, o una
+/// figura eliminada) se guardan en el comando.
+pub enum Command {
+    /// Agregar una figura en la posición `index` de la lista.
+    Add { index: usize, shape: Option<ShapeObject> },
+    /// Eliminar la figura en la posición `index` de la lista.
+    Delete { index: usize, shape: Option<ShapeObject> },
+    /// Mover o redimensionar la figura en la posición `index`.
+    Move { index: usize, from: Placement, to: Placement },
+    /// Cambiar el estilo de la figura en la posición `index`.
+    Restyle { index: usize, from: ShapeStyle, to: ShapeStyle },
+    /// Cambiar la figura en la posición `from` a la posición `to`, lo que cambia el orden en que se
+    /// dibujan las figuras.
+    Reorder { from: usize, to: usize },
+}
+
+impl Command {
+    /// Comando que agrega la figura dada en la posición `index` de la lista.
+    pub fn add(index: usize, shape: ShapeObject) -> Self { Command::Add { index, shape: Some(shape) } }
+
+    /// Comando que elimina la figura en la posición `index` de la lista.
+    pub fn delete(index: usize) -> Self { Command::Delete { index, shape: None } }
+
+    /// Comando que cambia el estilo de la figura dada, en la posición `index`, por `style`.
+    pub fn restyle(index: usize, shape: &ShapeObject, style: ShapeStyle) -> Self {
+        Command::Restyle { index, from: *shape.style(), to: style }
+    }
+
+    /// Aplica el comando a la lista de figuras.
+    pub fn apply(&mut self, shapes: &mut GlShapeList) {
+        match self {
+            Command::Add { index, shape } => insert(shapes, *index, shape),
+            Command::Delete { index, shape } => remove(shapes, *index, shape),
+            Command::Move { index, to, .. } => {
+                if let Some(shape) = shapes.get_mut(*index) {
+                    to.apply_to(shape);
+                }
+            }
+            Command::Restyle { index, to, .. } => {
+                if let Some(shape) = shapes.get_mut(*index) {
+                    *shape.style_mut() = *to;
+                }
+            }
+            Command::Reorder { from, to } => reorder(shapes, *from, *to),
+        }
+    }
+
+    /// Deshace el comando, devolviendo la lista de figuras al estado previo a [`Command::apply`].
+    pub fn revert(&mut self, shapes: &mut GlShapeList) {
+        match self {
+            Command::Add { index, shape } => remove(shapes, *index, shape),
+            Command::Delete { index, shape } => insert(shapes, *index, shape),
+            Command::Move { index, from, .. } => {
+                if let Some(shape) = shapes.get_mut(*index) {
+                    from.apply_to(shape);
+                }
+            }
+            Command::Restyle { index, from, .. } => {
+                if let Some(shape) = shapes.get_mut(*index) {
+                    *shape.style_mut() = *from;
+                }
+            }
+            Command::Reorder { from, to } => reorder(shapes, *to, *from),
+        }
+    }
+}
+
+/// Mueve la figura guardada a la lista, en la posición dada.
+fn insert(shapes: &mut GlShapeList, index: usize, stored: &mut Option<ShapeObject>) {
+    if let Some(shape) = stored.take() {
+        shapes.insert(index.min(shapes.len()), shape);
+    }
+}
+
+/// Quita la figura en la posición dada de la lista, y la guarda.
+fn remove(shapes: &mut GlShapeList, index: usize, stored: &mut Option<ShapeObject>) {
+    if index < shapes.len() {
+        *stored = Some(shapes.remove(index));
+    }
+}
+
+/// Cambia la figura en la posición `from` a la posición `to`.
+fn reorder(shapes: &mut GlShapeList, from: usize, to: usize) {
+    if from < shapes.len() && to < shapes.len() {
+        let shape = shapes.remove(from);
+        shapes.insert(to, shape);
+    }
+}
+
+/// Historial de comandos para deshacer y rehacer cambios.
+///
+/// El historial guarda a lo más [`History::limit`] comandos; al superarlo, se olvidan los más
+/// antiguos. Ejecutar un comando nuevo descarta los comandos deshechos.
+pub struct History {
+    undo: VecDeque<Command>,
+    redo: Vec<Command>,
+    limit: usize,
+}
+
+impl History {
+    /// Cantidad de comandos que se guardan por defecto.
+    pub const DEFAULT_LIMIT: usize = 100;
+
+    /// Crea un historial vacío que guarda a lo más `limit` comandos.
+    pub fn new(limit: usize) -> Self { Self { undo: VecDeque::new(), redo: Vec::new(), limit } }
+
+    /// Cantidad máxima de comandos que se pueden deshacer.
+    pub fn limit(&self) -> usize { self.limit }
+
+    /// Aplica el comando a la lista de figuras y lo guarda en el historial.
+    pub fn execute(&mut self, mut command: Command, shapes: &mut GlShapeList) {
+        command.apply(shapes);
+        self.record(command);
+    }
+
+    /// Guarda en el historial un comando que ya se aplicó a la lista de figuras.
+    ///
+    /// Útil para los cambios que se aplican poco a poco, como arrastrar una figura: el arrastre
+    /// completo se guarda como un solo comando al terminar.
+    pub fn record(&mut self, command: Command) {
+        self.redo.clear();
+        self.undo.push_back(command);
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Deshace el último comando. Devuelve `false` si no hay comandos que deshacer.
+    pub fn undo(&mut self, shapes: &mut GlShapeList) -> bool {
+        let Some(mut command) = self.undo.pop_back() else { return false };
+        command.revert(shapes);
+        self.redo.push(command);
+        true
+    }
+
+    /// Rehace el último comando deshecho. Devuelve `false` si no hay comandos que rehacer.
+    pub fn redo(&mut self, shapes: &mut GlShapeList) -> bool {
+        let Some(mut command) = self.redo.pop() else { return false };
+        command.apply(shapes);
+        self.undo.push_back(command);
+        true
+    }
+
+    /// Olvida todos los comandos.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Revisa si hay comandos que deshacer.
+    pub fn can_undo(&self) -> bool { !self.undo.is_empty() }
+
+    /// Revisa si hay comandos que rehacer.
+    pub fn can_redo(&self) -> bool { !self.redo.is_empty() }
+}
+
+impl Default for History {
+    fn default() -> Self { Self::new(Self::DEFAULT_LIMIT) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Command, History, Placement};
+    use crate::geometries::Transform;
+    use crate::{Color, GlShapeList, Shape, ShapeObject};
+
+    /// Centros de las figuras, para identificarlas.
+    fn centers(shapes: &GlShapeList) -> Vec<(i32, i32)> { shapes.iter().map(|shape| shape.center).collect() }
+
+    fn square(x: i32) -> ShapeObject { ShapeObject::new_square(4, (x, 0)) }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut shapes = vec![square(0), square(1)];
+        let mut history = History::default();
+
+        history.execute(Command::add(1, square(2)), &mut shapes);
+        history.execute(Command::delete(0), &mut shapes);
+        history.execute(Command::Reorder { from: 1, to: 0 }, &mut shapes);
+        assert_eq!(centers(&shapes), [(1, 0), (2, 0)]);
+
+        let style = shapes[0].style().fill_color(Color::BLACK);
+        history.execute(Command::restyle(0, &shapes[0], style), &mut shapes);
+        assert_eq!(shapes[0].style().fill_color, Some(Color::BLACK));
+
+        let from = Placement::of(&shapes[1]);
+        shapes[1].center = (5, 5);
+        shapes[1].transform = Transform::scale(2.0, 2.0);
+        history.record(Command::Move { index: 1, from, to: Placement::of(&shapes[1]) });
+
+        assert!(history.undo(&mut shapes));
+        assert_eq!(Placement::of(&shapes[1]), from);
+        assert!(history.undo(&mut shapes));
+        assert_eq!(shapes[0].style().fill_color, None);
+        assert!(history.undo(&mut shapes));
+        assert_eq!(centers(&shapes), [(2, 0), (1, 0)]);
+        assert!(history.undo(&mut shapes));
+        assert_eq!(centers(&shapes), [(0, 0), (2, 0), (1, 0)], "La figura eliminada vuelve a su posición");
+        assert!(history.undo(&mut shapes));
+        assert_eq!(centers(&shapes), [(0, 0), (1, 0)]);
+        assert!(!history.undo(&mut shapes));
+
+        while history.redo(&mut shapes) {}
+        assert_eq!(centers(&shapes), [(1, 0), (5, 5)]);
+        assert_eq!(shapes[1].transform, Transform::scale(2.0, 2.0));
+        assert_eq!(shapes[0].style().fill_color, Some(Color::BLACK));
+    }
+
+    #[test]
+    fn new_commands_discard_redo() {
+        let mut shapes = GlShapeList::new();
+        let mut history = History::default();
+        history.execute(Command::add(0, square(0)), &mut shapes);
+        history.undo(&mut shapes);
+        assert!(history.can_redo());
+
+        history.execute(Command::add(0, square(1)), &mut shapes);
+        assert!(!history.can_redo());
+        assert_eq!(centers(&shapes), [(1, 0)]);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut shapes = GlShapeList::new();
+        let mut history = History::new(3);
+        for x in 0..5 {
+            history.execute(Command::add(shapes.len(), square(x)), &mut shapes);
+        }
+
+        while history.undo(&mut shapes) {}
+        assert_eq!(centers(&shapes), [(0, 0), (1, 0)], "Solo se pueden deshacer los últimos 3 comandos");
+    }
+}
@@ -1,45 +1,97 @@
-use std::fs;
-
 pub use gl_window::GlWindow;
+use glium::winit::event_loop::EventLoop;
+pub use history::{Command, History, Placement};
+pub use selection::{Handle, Selection, SelectionDrag};
+pub use tool::Tool;
 
-use crate::{Figura, ShapeObject};
+use crate::ipc::Message;
+use crate::scene::{Scene, SceneWatcher};
+pub use crate::view::{Camera, shapes_bounds};
+use crate::{Color, ShapeObject};
 
 mod gl_window;
+mod history;
+mod selection;
+mod tool;
 
 /// Alias de: Lista de objetos a dibujar
 pub type GlShapeList = Vec<ShapeObject>;
 
+/// Abre la ventana de OpenGL con la escena guardada (ver [`Scene::load_default`]).
+///
+/// La ventana vuelve a leer el archivo de la escena (ver [`Scene::default_path`]) cada vez que
+/// cambia, por ejemplo al editarlo en un editor de texto o al guardarlo desde el panel de control.
+/// Cada recarga reemplaza la escena completa: se descartan las figuras dibujadas en la ventana y
+/// se vacía el historial para deshacer. Si la escena no se puede leer, se muestra el error en el
+/// registro y la ventana sigue con la escena anterior.
 pub fn run_loop_standalone() {
-    let event_loop = glium::winit::event_loop::EventLoop::new().unwrap();
+    let path = Scene::default_path();
+    let scene = Scene::load(path).unwrap_or_else(|e| {
+        tracing::error!("No se pudo leer la escena `{path}`: {e}");
+        empty_scene()
+    });
+    let event_loop = EventLoop::<Message>::with_user_event().build().unwrap();
+    let proxy = event_loop.create_proxy();
+    std::thread::spawn(move || {
+        // Se deja de revisar cuando la ventana se cierra
+        SceneWatcher::new(path).watch(|result| {
+            match result {
+                Ok(scene) => {
+                    tracing::info!("Escena `{path}` recargada");
+                    proxy.send_event(Message::LoadScene { scene }).is_ok()
+                }
+                Err(e) => {
+                    tracing::error!("No se pudo recargar la escena `{path}`: {e}");
+                    true
+                }
+            }
+        });
+    });
+    run_loop(event_loop, scene);
+}
+
+/// Abre la ventana de OpenGL vacía, y la actualiza con los mensajes que lleguen por la entrada
+/// estándar (ver [`crate::ipc`]).
+pub fn run_loop_ipc() {
+    let event_loop = EventLoop::<Message>::with_user_event().build().unwrap();
+    let proxy = event_loop.create_proxy();
+    std::thread::spawn(move || {
+        // Se deja de leer cuando la ventana se cierra
+        crate::ipc::read_messages(std::io::stdin().lock(), |message| proxy.send_event(message).is_ok());
+        tracing::info!("El panel de control cerró la conexión");
+    });
+    run_loop(event_loop, empty_scene());
+}
 
+/// Escena sin figuras, con el tamaño por defecto de la ventana.
+fn empty_scene() -> Scene { Scene::new((800, 600), Color::from_u32_rgb(0xFFFFFF)) }
+
+fn run_loop(event_loop: EventLoop<Message>, scene: Scene) {
     let (window, display) = glium::backend::glutin::SimpleWindowBuilder::new()
         .with_title(GlWindow::WINDOW_TITLE)
-        .with_inner_size(800, 600)
+        .with_inner_size(scene.canvas_size.0, scene.canvas_size.1)
         .build(&event_loop);
 
-    let config: crate::Config = serde_json::from_str(&fs::read_to_string("config.json").expect("No se pudo leer config.json"))
-        .expect("No se pudo deserializar config.json");
-
-    let stroke_color = config.stroke_color;
-    let background = config.shape_background_color;
-    let mut shapes = GlShapeList::new();
-
-    for figura in &config.figuras {
-        let mut shape = match figura {
-            Figura::Circulo => ShapeObject::new_circle(config.radio_circulo, config.centro_circulo),
-            Figura::Cuadrado => ShapeObject::new_square(config.cuadrado, config.centro_cuadrado),
-            Figura::Rectangulo => {
-                ShapeObject::new_rectangle(config.ancho_rectangulo, config.largo_rectangulo, config.centro_rectangulo)
-            }
-            Figura::Elipse => ShapeObject::new_ellipse(config.radio1_elipse, config.radio2_elipse, config.centro_elipse),
-        };
-        *shape.style_mut() = shape.style_mut().stroke_color(stroke_color).fill_color(background).stroke_width(config.grosor);
-        shapes.push(shape);
-    }
-    // Editar configuracion de estilo
-
-    let shapes_list = shapes;
+    let shapes_list = scene.to_shapes();
+    // Las herramientas empiezan con el estilo de la última figura de la escena
+    let tool_style = scene.shapes.last().map(|shape| shape.style).unwrap_or_default();
 
-    let mut this = GlWindow { program: None, display, window, shapes_list, background_color: config.background_color };
+    let mut this = GlWindow {
+        program: None,
+        display,
+        window,
+        shapes_list,
+        background_color: scene.background_color,
+        modifiers: Default::default(),
+        tool: Tool::default(),
+        tool_style,
+        cursor_position: (0, 0),
+        camera: Camera::default(),
+        panning: false,
+        drag_start: None,
+        selection: None,
+        history: History::default(),
+        screenshot_format: image::ImageFormat::Png,
+    };
     event_loop.run_app(&mut this).unwrap();
 }
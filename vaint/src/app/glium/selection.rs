@@ -0,0 +1,247 @@
+//! Selección y edición de figuras con la herramienta de puntero.
+use super::Placement;
+use crate::algorithms::ClipRect;
+use crate::geometries::Transform;
+use crate::{Color, PixelCoord, Shape, ShapeObject, ShapeStyle};
+
+/// Color de la caja de selección y de sus asas.
+pub const SELECTION_COLOR: Color = Color::from_u32_rgb(0x1E90FF);
+
+/// Figura seleccionada en la ventana de OpenGL.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Selection {
+    /// Índice de la figura en [`super::GlWindow::shapes_list`].
+    pub index: usize,
+    /// Edición en curso, mientras se arrastra con el botón izquierdo del ratón.
+    pub drag: Option<SelectionDrag>,
+    /// Caja delimitadora de la figura. Se calcula al seleccionarla y al editarla, y no en cada
+    /// cuadro, ya que algunas figuras tienen que generar sus pixeles para calcularla.
+    pub bounds: Option<ClipRect>,
+}
+
+impl Selection {
+    /// Selecciona la figura dada, que está en la posición `index` de la lista, sin ninguna edición
+    /// en curso.
+    pub fn new(index: usize, shape: &ShapeObject) -> Self { Self { index, drag: None, bounds: shape.bounding_box() } }
+
+    /// Aplica el arrastre en curso a la figura seleccionada, `shape`, hasta el pixel `cursor` de la
+    /// escena, y actualiza la caja delimitadora.
+    ///
+    /// Al mover la figura la caja solo se desplaza, así que no se vuelve a calcular.
+    pub fn drag_to(&mut self, shape: &mut ShapeObject, cursor: PixelCoord) {
+        let Some(drag) = self.drag else { return };
+        let before = shape.center;
+        drag.apply(shape, cursor);
+        self.bounds = match drag {
+            SelectionDrag::Move { .. } => {
+                let (dx, dy) = (shape.center.0 - before.0, shape.center.1 - before.1);
+                self.bounds.map(|b| ClipRect::new((b.x_min + dx, b.y_min + dy), (b.x_max + dx, b.y_max + dy)))
+            }
+            SelectionDrag::Resize { .. } => shape.bounding_box(),
+        };
+    }
+}
+
+/// Edición de la figura seleccionada arrastrando el cursor.
+///
+/// Guarda el estado de la figura al empezar el arrastre, para que la edición no acumule errores de
+/// redondeo mientras el cursor se mueve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionDrag {
+    /// Mover la figura. El arrastre empezó en el pixel `start`, con la figura centrada en `center`.
+    Move { start: PixelCoord, center: PixelCoord },
+    /// Redimensionar la figura arrastrando un asa de su caja delimitadora `bounds`. La figura tenía
+    /// el centro `center` y la transformación `transform`.
+    Resize { handle: Handle, bounds: ClipRect, center: PixelCoord, transform: Transform },
+}
+
+impl SelectionDrag {
+    /// Posición y transformación que tenía la figura dada al empezar el arrastre.
+    pub fn origin(&self, shape: &ShapeObject) -> Placement {
+        match *self {
+            SelectionDrag::Move { center, .. } => Placement { center, transform: shape.transform },
+            SelectionDrag::Resize { center, transform, .. } => Placement { center, transform },
+        }
+    }
+
+    /// Aplica a la figura el arrastre del cursor hasta el pixel `cursor` de la escena.
+    pub fn apply(&self, shape: &mut ShapeObject, cursor: PixelCoord) {
+        match *self {
+            SelectionDrag::Move { start, center } => {
+                shape.center = (center.0 + cursor.0 - start.0, center.1 + cursor.1 - start.1);
+            }
+            SelectionDrag::Resize { handle, bounds, center, transform } => {
+                let (sx, sy) = handle.scale(&bounds, cursor);
+                let (ax, ay) = handle.anchor(&bounds);
+                // El centro se escala respecto al asa opuesta, que queda fija
+                let scaled = |c: i32, a: i32, s: f32| (a as f32 + (c - a) as f32 * s).round() as i32;
+                shape.center = (scaled(center.0, ax, sx), scaled(center.1, ay, sy));
+                shape.transform = transform.then(Transform::scale(sx, sy));
+            }
+        }
+    }
+}
+
+/// Asa de la caja de selección, en una esquina o en el punto medio de un lado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handle {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+}
+
+impl Handle {
+    /// Todas las asas, en sentido horario desde la esquina superior izquierda.
+    pub const ALL: [Self; 8] = [
+        Handle::TopLeft,
+        Handle::Top,
+        Handle::TopRight,
+        Handle::Right,
+        Handle::BottomRight,
+        Handle::Bottom,
+        Handle::BottomLeft,
+        Handle::Left,
+    ];
+
+    /// Lado de la caja en el que está el asa en cada eje: -1 para el mínimo, 1 para el máximo y 0
+    /// para el punto medio.
+    fn direction(self) -> (i32, i32) {
+        match self {
+            Handle::TopLeft => (-1, -1),
+            Handle::Top => (0, -1),
+            Handle::TopRight => (1, -1),
+            Handle::Right => (1, 0),
+            Handle::BottomRight => (1, 1),
+            Handle::Bottom => (0, 1),
+            Handle::BottomLeft => (-1, 1),
+            Handle::Left => (-1, 0),
+        }
+    }
+
+    /// Posición del asa en la caja dada.
+    pub fn position(self, bounds: &ClipRect) -> PixelCoord {
+        let (dx, dy) = self.direction();
+        (side(dx, bounds.x_min, bounds.x_max), side(dy, bounds.y_min, bounds.y_max))
+    }
+
+    /// Punto de la caja que queda fijo al arrastrar el asa: la posición del asa opuesta.
+    pub fn anchor(self, bounds: &ClipRect) -> PixelCoord {
+        let (dx, dy) = self.direction();
+        (side(-dx, bounds.x_min, bounds.x_max), side(-dy, bounds.y_min, bounds.y_max))
+    }
+
+    /// Factores de escala, respecto a [`Handle::anchor`], que llevan el asa de la caja dada hasta
+    /// el pixel `target`. Las asas de los lados solo escalan en un eje.
+    ///
+    /// La caja nunca se reduce a menos de un pixel, para que la transformación siga siendo
+    /// invertible; arrastrar el asa más allá del asa opuesta refleja la figura.
+    pub fn scale(self, bounds: &ClipRect, target: PixelCoord) -> (f32, f32) {
+        let (handle, anchor) = (self.position(bounds), self.anchor(bounds));
+        let axis = |handle: i32, anchor: i32, target: i32| {
+            let span = (handle - anchor) as f32;
+            if span == 0.0 {
+                return 1.0;
+            }
+            let scale = (target - anchor) as f32 / span;
+            match (scale * span).abs() < 1.0 {
+                true => 1.0 / span.abs(),
+                false => scale,
+            }
+        };
+        (axis(handle.0, anchor.0, target.0), axis(handle.1, anchor.1, target.1))
+    }
+
+    /// Revisa si el pixel dado está sobre el asa de la caja dada, dibujada como un cuadrado de
+    /// `size` pixeles de lado.
+    pub fn contains(self, bounds: &ClipRect, size: u32, (x, y): PixelCoord) -> bool {
+        let (hx, hy) = self.position(bounds);
+        let reach = (size / 2) as i32;
+        (x - hx).abs() <= reach && (y - hy).abs() <= reach
+    }
+}
+
+/// Coordenada de un lado de la caja en un eje, según la dirección de [`Handle::direction`].
+fn side(direction: i32, min: i32, max: i32) -> i32 {
+    match direction.signum() {
+        -1 => min,
+        1 => max,
+        _ => (min + max) / 2,
+    }
+}
+
+/// Figuras que dibujan la caja de selección alrededor de `bounds`, con un cuadrado de `handle_size`
+/// pixeles de lado en cada asa.
+pub fn selection_overlay(bounds: &ClipRect, handle_size: u32) -> Vec<ShapeObject> {
+    // La caja se dibuja un pixel por fuera de la figura, para no taparla
+    let (width, height) = ((bounds.x_max - bounds.x_min + 2) as u32, (bounds.y_max - bounds.y_min + 2) as u32);
+    let center = (bounds.x_min - 1 + width as i32 / 2, bounds.y_min - 1 + height as i32 / 2);
+    let mut overlay = vec![ShapeObject::new_rectangle(width, height, center)];
+    *overlay[0].style_mut() = ShapeStyle::default().stroke_color(SELECTION_COLOR);
+
+    for handle in Handle::ALL {
+        let mut square = ShapeObject::new_square(handle_size, handle.position(bounds));
+        *square.style_mut() = ShapeStyle::default().stroke_color(SELECTION_COLOR).fill_color(0xFFFFFF);
+        overlay.push(square);
+    }
+    overlay
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Handle, SelectionDrag};
+    use crate::algorithms::ClipRect;
+    use crate::{Shape, ShapeObject};
+
+    #[test]
+    fn handles_and_anchors() {
+        let bounds = ClipRect::new((10, 20), (30, 40));
+        assert_eq!(Handle::TopLeft.position(&bounds), (10, 20));
+        assert_eq!(Handle::TopLeft.anchor(&bounds), (30, 40));
+        assert_eq!(Handle::Right.position(&bounds), (30, 30));
+        assert_eq!(Handle::Right.anchor(&bounds), (10, 30));
+
+        assert_eq!(Handle::BottomRight.scale(&bounds, (50, 50)), (2.0, 1.5));
+        assert_eq!(Handle::Bottom.scale(&bounds, (50, 50)), (1.0, 1.5));
+        assert_eq!(Handle::Left.scale(&bounds, (40, 0)), (-0.5, 1.0), "Pasar el asa opuesta refleja la figura");
+        assert_eq!(Handle::Left.scale(&bounds, (30, 0)), (0.05, 1.0), "La caja no baja de un pixel");
+
+        assert!(Handle::Top.contains(&bounds, 6, (23, 17)));
+        assert!(!Handle::Top.contains(&bounds, 6, (23, 16)));
+    }
+
+    #[test]
+    fn hit_testing() {
+        let circle = ShapeObject::new_circle(10, (50, 50));
+        assert!(circle.hit_outline((60, 50), 0.0));
+        assert!(circle.hit_outline((62, 51), 2.5));
+        assert!(!circle.hit_outline((63, 50), 2.0));
+        assert!(circle.contains_point((50, 50)));
+        assert!(!circle.contains_point((62, 50)));
+
+        // Las líneas no tienen relleno, solo se seleccionan por su borde
+        let line = ShapeObject::new_line((0, 0), (20, 0), Default::default());
+        assert!(!line.contains_point((10, 0)));
+        assert!(line.hit_outline((10, 2), 2.0));
+    }
+
+    #[test]
+    fn drag_moves_and_resizes() {
+        let mut shape = ShapeObject::new_rectangle(20, 10, (20, 15));
+        let bounds = shape.bounding_box().unwrap();
+        assert_eq!(bounds, ClipRect::new((10, 10), (30, 20)));
+
+        let resize =
+            SelectionDrag::Resize { handle: Handle::BottomRight, bounds, center: shape.center, transform: shape.transform };
+        resize.apply(&mut shape, (50, 30));
+        assert_eq!(shape.bounding_box(), Some(ClipRect::new((10, 10), (50, 30))));
+
+        let start = (12, 12);
+        SelectionDrag::Move { start, center: shape.center }.apply(&mut shape, (17, 9));
+        assert_eq!(shape.bounding_box(), Some(ClipRect::new((15, 7), (55, 27))));
+    }
+}
@@ -0,0 +1,85 @@
+//! Herramientas de edición disponibles en la ventana de OpenGL.
+use crate::algorithms::{Connectivity, LineAlgorithm};
+use crate::{PixelCoord, ShapeObject};
+
+/// Herramienta activa en la ventana de OpenGL.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    /// Selecciona figuras, y las mueve o redimensiona arrastrándolas (ver [`super::Selection`]).
+    #[default]
+    Pointer,
+    /// Rellena la región del color bajo el cursor (cubeta). Con `Shift`, rellena hasta el color de
+    /// borde de [`super::GlWindow::tool_style`].
+    Bucket,
+    /// Crea un rectángulo arrastrando de una esquina a la opuesta.
+    Rectangle,
+    /// Crea un círculo arrastrando desde su centro; la distancia arrastrada es el radio.
+    Circle,
+    /// Crea una elipse inscrita en el rectángulo arrastrado.
+    Ellipse,
+    /// Crea una línea arrastrando de un extremo al otro.
+    Line,
+}
+
+impl Tool {
+    /// Conectividad utilizada por la cubeta. Con conectividad 4 el relleno no se escapa por los
+    /// huecos diagonales de los contornos de 1 pixel.
+    pub const BUCKET_CONNECTIVITY: Connectivity = Connectivity::Four;
+
+    /// Devuelve la herramienta asociada a la tecla dada.
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_ascii_lowercase().as_str() {
+            "p" => Some(Tool::Pointer),
+            "b" => Some(Tool::Bucket),
+            "r" => Some(Tool::Rectangle),
+            "c" => Some(Tool::Circle),
+            "e" => Some(Tool::Ellipse),
+            "l" => Some(Tool::Line),
+            _ => None,
+        }
+    }
+
+    /// Crea la figura que resulta de arrastrar el cursor de `start` a `end`, en coordenadas de la
+    /// escena. Devuelve `None` si la herramienta no crea figuras o si el arrastre no tiene tamaño.
+    pub fn shape_from_drag(self, start: PixelCoord, end: PixelCoord) -> Option<ShapeObject> {
+        if start == end {
+            return None;
+        }
+        let (x_min, y_min) = (start.0.min(end.0), start.1.min(end.1));
+        let (width, height) = (start.0.abs_diff(end.0), start.1.abs_diff(end.1));
+        let center = (x_min + width as i32 / 2, y_min + height as i32 / 2);
+
+        match self {
+            Tool::Pointer | Tool::Bucket => None,
+            Tool::Rectangle => Some(ShapeObject::new_rectangle(width, height, center)),
+            Tool::Circle => {
+                let radius = (width as f32).hypot(height as f32).round() as u32;
+                Some(ShapeObject::new_circle(radius, start))
+            }
+            Tool::Ellipse => Some(ShapeObject::new_ellipse(width / 2, height / 2, center)),
+            Tool::Line => Some(ShapeObject::new_line(start, end, LineAlgorithm::default())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tool;
+    use crate::Shape;
+    use crate::algorithms::ClipRect;
+
+    #[test]
+    fn drag_creates_shapes() {
+        let bounds = |tool: Tool, start, end| tool.shape_from_drag(start, end).and_then(|shape| shape.bounding_box());
+
+        // El arrastre puede ir en cualquier dirección
+        assert_eq!(bounds(Tool::Rectangle, (10, 20), (30, 25)), Some(ClipRect::new((10, 20), (30, 25))));
+        assert_eq!(bounds(Tool::Rectangle, (30, 25), (10, 20)), Some(ClipRect::new((10, 20), (30, 25))));
+        assert_eq!(bounds(Tool::Line, (30, 5), (10, 20)), Some(ClipRect::new((10, 5), (30, 20))));
+        assert_eq!(bounds(Tool::Circle, (50, 50), (53, 54)), Some(ClipRect::new((45, 45), (55, 55))));
+        assert_eq!(bounds(Tool::Ellipse, (10, 10), (30, 20)), Some(ClipRect::new((10, 10), (30, 20))));
+
+        assert!(Tool::Rectangle.shape_from_drag((5, 5), (5, 5)).is_none(), "Un clic sin arrastre no crea figuras");
+        assert!(Tool::Bucket.shape_from_drag((0, 0), (5, 5)).is_none());
+    }
+}
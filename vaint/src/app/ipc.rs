@@ -0,0 +1,185 @@
+//! Comunicación entre el panel de control y la ventana de OpenGL.
+//!
+//! El panel inicia la ventana como un proceso aparte (ambas aplicaciones necesitan su propio
+//! `EventLoop` de winit en el hilo principal) con el argumento [`IPC_FLAG`], y le envía
+//! [`Message`]s por su entrada estándar, uno por línea en JSON. Así, los cambios del panel se ven
+//! en la ventana mientras se editan.
+use std::io::{self, BufRead, Write};
+use std::process::{Child, ChildStdin, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Color;
+use crate::scene::{Scene, SceneShape};
+
+/// Argumento con el que se inicia la ventana de OpenGL para que lea mensajes de su entrada
+/// estándar.
+pub const IPC_FLAG: &str = "--ipc";
+
+/// Nombre del ejecutable de la ventana de OpenGL, sin la extensión de la plataforma.
+pub const GL_WINDOW_BINARY: &str = "opengl_app";
+
+/// Identificador de una figura del panel de control, que no cambia aunque la ventana agregue,
+/// elimine o reordene sus propias figuras.
+pub type ShapeId = u64;
+
+/// Mensaje del panel de control a la ventana de OpenGL.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Reemplaza todas las figuras de la ventana por las de la escena. Cada figura recibe como
+    /// identificador su posición en la escena.
+    LoadScene { scene: Scene },
+    /// Agrega una figura encima de las demás.
+    AddShape { id: ShapeId, shape: SceneShape },
+    /// Reemplaza la figura con el identificador dado, o la agrega si no existe.
+    UpdateShape { id: ShapeId, shape: SceneShape },
+    /// Elimina la figura con el identificador dado.
+    RemoveShape { id: ShapeId },
+    /// Cambia el color de fondo de la ventana.
+    SetBackground { color: Color },
+}
+
+impl Message {
+    /// Escribe el mensaje como una línea de JSON, sin el salto de línea.
+    pub fn to_line(&self) -> String { serde_json::to_string(self).expect("Los mensajes siempre se pueden serializar") }
+
+    /// Lee un mensaje de una línea de JSON.
+    pub fn from_line(line: &str) -> Result<Self, serde_json::Error> { serde_json::from_str(line) }
+}
+
+/// Mensajes que llevan la ventana de la escena `old` a la escena `new`, tomando como identificador
+/// de cada figura su posición en la escena.
+///
+/// Solo se envían las figuras que cambiaron. El tamaño del lienzo no se actualiza; para eso se
+/// envía la escena completa con [`Message::LoadScene`].
+pub fn scene_updates(old: &Scene, new: &Scene) -> Vec<Message> {
+    let mut messages = Vec::new();
+    if old.background_color != new.background_color {
+        messages.push(Message::SetBackground { color: new.background_color });
+    }
+
+    for (id, (old_shape, new_shape)) in (0..).zip(old.shapes.iter().zip(&new.shapes)) {
+        if old_shape != new_shape {
+            messages.push(Message::UpdateShape { id, shape: new_shape.clone() });
+        }
+    }
+    for (id, shape) in (0..).zip(&new.shapes).skip(old.shapes.len()) {
+        messages.push(Message::AddShape { id, shape: shape.clone() });
+    }
+    // Las figuras se eliminan de la última a la primera
+    for id in (new.shapes.len()..old.shapes.len()).rev() {
+        messages.push(Message::RemoveShape { id: id as ShapeId });
+    }
+    messages
+}
+
+/// Lee mensajes de `reader`, uno por línea, y llama a `handle` con cada uno, hasta que se termine
+/// la entrada o `handle` devuelva `false`. Las líneas que no son mensajes válidos se ignoran con un
+/// aviso.
+pub fn read_messages(reader: impl BufRead, mut handle: impl FnMut(Message) -> bool) {
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::error!("No se pudo leer el mensaje: {e}");
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Message::from_line(&line) {
+            Ok(message) => {
+                if !handle(message) {
+                    break;
+                }
+            }
+            Err(e) => tracing::warn!("Mensaje inválido `{line}`: {e}"),
+        }
+    }
+}
+
+/// Proceso de la ventana de OpenGL, iniciado por el panel de control.
+pub struct GlWindowProcess {
+    child: Child,
+    stdin: ChildStdin,
+}
+
+impl GlWindowProcess {
+    /// Inicia la ventana de OpenGL con [`IPC_FLAG`]. El ejecutable se busca junto al ejecutable
+    /// actual, con la extensión de la plataforma.
+    pub fn spawn() -> io::Result<Self> {
+        let path = std::env::current_exe()?.with_file_name(format!("{GL_WINDOW_BINARY}{}", std::env::consts::EXE_SUFFIX));
+        let mut child = std::process::Command::new(path).arg(IPC_FLAG).stdin(Stdio::piped()).spawn()?;
+        let stdin = child.stdin.take().expect("La entrada estándar del proceso es un pipe");
+        Ok(Self { child, stdin })
+    }
+
+    /// Envía el mensaje a la ventana. Falla si la ventana ya se cerró.
+    pub fn send(&mut self, message: &Message) -> io::Result<()> {
+        writeln!(self.stdin, "{}", message.to_line())?;
+        self.stdin.flush()
+    }
+
+    /// Revisa si la ventana sigue abierta.
+    pub fn is_running(&mut self) -> bool { matches!(self.child.try_wait(), Ok(None)) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Message, read_messages, scene_updates};
+    use crate::Color;
+    use crate::scene::{Geometry, Scene, SceneShape};
+
+    fn circle(radius: u32) -> SceneShape { SceneShape::new(Geometry::Circle { radius, center: (10, 10) }, Default::default()) }
+
+    #[test]
+    fn messages_are_json_lines() {
+        let message = Message::UpdateShape { id: 3, shape: circle(5) };
+        let line = message.to_line();
+        assert!(!line.contains('\n'));
+        assert!(line.starts_with(r#"{"type":"update_shape","id":3,"shape":{"kind":"circle""#), "{line}");
+        assert_eq!(Message::from_line(&line).unwrap(), message);
+    }
+
+    #[test]
+    fn scene_updates_send_changes_only() {
+        let mut old = Scene::new((100, 100), Color::from_u32_rgb(0xFFFFFF));
+        old.shapes = vec![circle(1), circle(2), circle(3)];
+        let mut new = old.clone();
+        assert!(scene_updates(&old, &new).is_empty());
+
+        new.background_color = Color::BLACK;
+        new.shapes[1] = circle(20);
+        new.shapes.push(circle(4));
+        assert_eq!(scene_updates(&old, &new), [
+            Message::SetBackground { color: Color::BLACK },
+            Message::UpdateShape { id: 1, shape: circle(20) },
+            Message::AddShape { id: 3, shape: circle(4) },
+        ]);
+
+        new.shapes.truncate(1);
+        assert_eq!(scene_updates(&old, &new), [
+            Message::SetBackground { color: Color::BLACK },
+            Message::RemoveShape { id: 2 },
+            Message::RemoveShape { id: 1 }
+        ]);
+    }
+
+    #[test]
+    fn reads_messages_by_line() {
+        let input = format!(
+            "{}\nesto no es un mensaje\n\n{}\n{}\n",
+            Message::RemoveShape { id: 1 }.to_line(),
+            Message::SetBackground { color: Color::BLACK }.to_line(),
+            Message::RemoveShape { id: 2 }.to_line()
+        );
+        let mut received = Vec::new();
+        read_messages(input.as_bytes(), |message| {
+            received.push(message);
+            received.len() < 2
+        });
+        assert_eq!(received, [Message::RemoveShape { id: 1 }, Message::SetBackground { color: Color::BLACK }]);
+    }
+}
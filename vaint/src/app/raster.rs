@@ -0,0 +1,281 @@
+//! Renderizado por software (CPU) de las figuras.
+//!
+//! Permite obtener una imagen de la escena sin necesidad de un contexto de OpenGL, útil para
+//! pruebas y exportaciones en equipos sin GPU.
+use std::path::Path;
+
+use image::{ImageResult, RgbImage};
+
+use crate::algorithms::{ClipRect, CoveragePoint};
+use crate::view::Camera;
+use crate::{Color, GlShapeList, PixelCoord, Shape, ShapeObject, ShapeStyle};
+
+/// Renderiza la lista de figuras en una imagen RGB de las dimensiones dadas.
+///
+/// Dibuja los mismos puntos que [`crate::GlWindow`], generados por [`generate_shapes`]: primero el
+/// relleno de todas las figuras, y luego sus bordes, generados con su grosor por
+/// [`crate::Shape::write_stroke_points_at`]. La cobertura de los bordes con antialiasing se mezcla
+/// con el color ya presente en la imagen.
+pub fn render(shapes_list: &GlShapeList, background_color: Color, (width, height): (u32, u32)) -> RgbImage {
+    render_region(shapes_list, background_color, &ClipRect::from_dimensions((width as i32, height as i32)))
+}
+
+/// Renderiza solo el rectángulo dado de la escena, igual que [`render`]. El pixel `(0, 0)` de la
+/// imagen corresponde a la esquina superior izquierda del rectángulo.
+pub fn render_region(shapes_list: &GlShapeList, background_color: Color, region: &ClipRect) -> RgbImage {
+    let (width, height) = ((region.x_max - region.x_min + 1).max(0), (region.y_max - region.y_min + 1).max(0));
+    let mut image = RgbImage::from_pixel(width as u32, height as u32, background_color.into());
+    let origin = (region.x_min, region.y_min);
+
+    let shape_points = generate_shapes(shapes_list, region);
+
+    // Dibujar el relleno de las figuras
+    for ShapePoints { fill, style, .. } in shape_points.iter() {
+        let Some(color) = style.fill_color else { continue };
+        for &point in fill {
+            put_pixel(&mut image, relative(point, origin), color);
+        }
+    }
+
+    // Dibujar el contorno de las figuras
+    for ShapePoints { outline, style, .. } in shape_points.iter() {
+        let Some(color) = style.stroke_color else { continue };
+        for &(point, coverage) in outline {
+            blend_pixel(&mut image, relative(point, origin), color, coverage);
+        }
+    }
+    image
+}
+
+/// Renderiza la escena tal como se ve con la cámara dada en una ventana de las dimensiones dadas.
+///
+/// Se renderiza solo la región visible de la escena, y cada pixel de la imagen toma el color del
+/// pixel de la escena que se ve en él, por lo que con acercamiento cada pixel de la escena se ve
+/// como un cuadrado, igual que en [`crate::GlWindow`].
+pub fn render_view(shapes_list: &GlShapeList, background_color: Color, camera: &Camera, (width, height): (u32, u32)) -> RgbImage {
+    let region = camera.visible_rect((width, height));
+    let scene = render_region(shapes_list, background_color, &region);
+    RgbImage::from_fn(width, height, |x, y| {
+        let (x, y) = relative(camera.screen_to_world_pixel((x as i32, y as i32)), (region.x_min, region.y_min));
+        scene.get_pixel_checked(x as u32, y as u32).copied().unwrap_or(background_color.into())
+    })
+}
+
+/// Puntos generados para dibujar una figura.
+pub struct ShapePoints {
+    /// Pixeles que cubre el borde de la figura con su grosor, junto con su cobertura. La cobertura
+    /// es completa si la figura no tiene antialiasing.
+    pub outline: Vec<CoveragePoint>,
+    /// Puntos que forman el relleno de la figura. Vacío si la figura no tiene color de relleno.
+    pub fill: Vec<PixelCoord>,
+    pub style: ShapeStyle,
+}
+
+/// Genera los puntos del contorno y del relleno de cada figura a dibujar, junto con su estilo. Lo
+/// usan tanto este módulo como [`crate::GlWindow`], que envía los puntos a OpenGL.
+///
+/// Solo se generan los puntos dentro del rectángulo visible de la escena.
+pub fn generate_shapes(shapes_list: &[ShapeObject], screen: &ClipRect) -> Vec<ShapePoints> {
+    let screen = *screen;
+    let mut vertices: Vec<ShapePoints> = Vec::with_capacity(shapes_list.len());
+
+    for shape in shapes_list
+        .iter()
+        // Excluir las figuras que no se verían en pantalla.
+        .filter(|obj| !obj.style().is_transparent())
+    {
+        let style = *shape.style();
+        // Con una región de recorte, solo es visible su intersección con la pantalla
+        let (clip, region) = match &shape.clip_region {
+            None => (screen, None),
+            Some(region) => {
+                match region.visible_bounds(&screen) {
+                    Some(bounds) => (bounds, Some(region)),
+                    None => continue,
+                }
+            }
+        };
+        let visible = |point: PixelCoord| clip.contains(point) && region.is_none_or(|region| region.contains(point));
+
+        let mut outline: Vec<CoveragePoint> = Vec::new();
+        if style.stroke_color.is_some() && style.stroke_width <= 1.0 && style.anti_aliasing {
+            shape.write_aa_outline_points(&mut outline);
+            outline.retain(|&(point, _)| visible(point));
+        } else if style.stroke_color.is_some() {
+            let mut points: Vec<PixelCoord> = Vec::new();
+            // Los bordes gruesos se generan geométricamente, con sus uniones
+            match style.stroke_width > 1.0 {
+                true => {
+                    shape.write_stroke_points(&mut points);
+                    points.retain(|&point| visible(point));
+                }
+                // Los bordes delgados solo generan los puntos dentro del rectángulo visible
+                false => {
+                    shape.write_clipped_outline_points(&mut points, &clip);
+                    if let Some(region) = region {
+                        points.retain(|&point| region.contains(point));
+                    }
+                }
+            }
+            outline.extend(points.into_iter().map(|point| (point, 1.0)));
+        }
+
+        let mut fill: Vec<PixelCoord> = Vec::new();
+        if style.fill_color.is_some() {
+            shape.write_fill_points(&mut fill);
+            fill.retain(|&point| visible(point));
+        }
+        vertices.push(ShapePoints { outline, fill, style });
+    }
+    vertices
+}
+
+/// Renderiza la lista de figuras como se ve con la cámara dada (ver [`render_view`]) y guarda la
+/// imagen resultante en la ruta dada. Con [`Camera::default`] la imagen es la escena sin
+/// transformar, desde el origen.
+///
+/// El formato de la imagen (PNG, JPEG, BMP, ...) se deduce de la extensión de la ruta.
+pub fn export(
+    shapes_list: &GlShapeList,
+    background_color: Color,
+    camera: &Camera,
+    dimensions: (u32, u32),
+    path: impl AsRef<Path>,
+) -> ImageResult<()> {
+    render_view(shapes_list, background_color, camera, dimensions).save(path)
+}
+
+/// Posición del punto dado respecto al origen dado.
+fn relative((x, y): PixelCoord, (x0, y0): PixelCoord) -> PixelCoord { (x - x0, y - y0) }
+
+/// Colorea el pixel dado, ignorando los que estén fuera de la imagen.
+fn put_pixel(image: &mut RgbImage, (x, y): PixelCoord, color: Color) {
+    if x < 0 || y < 0 || x as u32 >= image.width() || y as u32 >= image.height() {
+        return;
+    }
+    image.put_pixel(x as u32, y as u32, color.into());
+}
+
+/// Mezcla el color dado con el del pixel según la cobertura, ignorando los pixeles que estén fuera
+/// de la imagen.
+fn blend_pixel(image: &mut RgbImage, (x, y): PixelCoord, color: Color, coverage: f32) {
+    if x < 0 || y < 0 || x as u32 >= image.width() || y as u32 >= image.height() {
+        return;
+    }
+    let coverage = coverage.clamp(0.0, 1.0);
+    let new = image::Rgb::<u8>::from(color);
+    let pixel = image.get_pixel_mut(x as u32, y as u32);
+    for (old, new) in pixel.0.iter_mut().zip(new.0) {
+        *old = (*old as f32 * (1.0 - coverage) + new as f32 * coverage).round() as u8;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use image::Rgb;
+
+    use super::{render, render_view};
+    use crate::algorithms::{ClipRect, LineAlgorithm};
+    use crate::view::Camera;
+    use crate::{Color, ShapeObject};
+
+    const WHITE: Color = Color::from_u32_rgb(0xFFFFFF);
+    const RED: Color = Color::from_u32_rgb(0xFF0000);
+    const BLUE: Color = Color::from_u32_rgb(0x0000FF);
+
+    #[test]
+    fn renders_background() {
+        let image = render(&vec![], WHITE, (16, 8));
+        assert_eq!(image.dimensions(), (16, 8));
+        assert!(image.pixels().all(|p| *p == Rgb(WHITE.0)));
+    }
+
+    #[test]
+    fn renders_filled_square() {
+        let mut square = ShapeObject::new_square(10, (20, 20));
+        *square.style_mut() = square.style_mut().stroke_color(RED).fill_color(BLUE);
+        let image = render(&vec![square], WHITE, (40, 40));
+
+        assert_eq!(*image.get_pixel(15, 15), Rgb(RED.0), "Esquina superior izquierda");
+        assert_eq!(*image.get_pixel(25, 25), Rgb(RED.0), "Esquina inferior derecha");
+        assert_eq!(*image.get_pixel(20, 20), Rgb(BLUE.0), "Centro del cuadrado");
+        assert_eq!(*image.get_pixel(5, 5), Rgb(WHITE.0), "Fuera del cuadrado");
+    }
+
+    #[test]
+    fn stroke_width_widens_outline() {
+        let mut square = ShapeObject::new_square(10, (20, 20));
+        *square.style_mut() = square.style_mut().stroke_color(RED).stroke_width(3.0);
+        let image = render(&vec![square], WHITE, (40, 40));
+
+        assert_eq!(*image.get_pixel(14, 20), Rgb(RED.0));
+        assert_eq!(*image.get_pixel(16, 20), Rgb(RED.0));
+        assert_eq!(*image.get_pixel(13, 20), Rgb(WHITE.0));
+        assert_eq!(*image.get_pixel(17, 20), Rgb(WHITE.0));
+    }
+
+    #[test]
+    fn renders_region_of_scene() {
+        let mut square = ShapeObject::new_square(10, (20, 20));
+        *square.style_mut() = square.style_mut().stroke_color(RED).fill_color(BLUE);
+        let shapes = vec![square];
+        let full = render(&shapes, WHITE, (40, 40));
+        let region = super::render_region(&shapes, WHITE, &ClipRect::new((12, 10), (31, 24)));
+
+        assert_eq!(region.dimensions(), (20, 15));
+        for (x, y, pixel) in region.enumerate_pixels() {
+            assert_eq!(pixel, full.get_pixel(x + 12, y + 10), "Pixel ({x}, {y}) de la región");
+        }
+    }
+
+    #[test]
+    fn renders_camera_view() {
+        let mut square = ShapeObject::new_square(10, (20, 20));
+        *square.style_mut() = square.style_mut().stroke_color(RED).fill_color(BLUE);
+        let shapes = vec![square];
+        let full = render(&shapes, WHITE, (40, 40));
+        assert_eq!(render_view(&shapes, WHITE, &Camera::default(), (40, 40)), full);
+
+        // Cada pixel de la escena se ve como un cuadrado de 3x3 pixeles
+        let camera = Camera { offset: (12.0, 10.0), zoom: 3.0 };
+        let view = render_view(&shapes, WHITE, &camera, (60, 45));
+        for (x, y, pixel) in view.enumerate_pixels() {
+            assert_eq!(pixel, full.get_pixel(12 + x / 3, 10 + y / 3), "Pixel ({x}, {y}) de la vista");
+        }
+    }
+
+    #[test]
+    fn exports_rendered_image() {
+        let mut circle = ShapeObject::new_circle(8, (10, 10));
+        *circle.style_mut() = circle.style_mut().stroke_color(RED).fill_color(BLUE);
+        let shapes = vec![circle];
+        let path = std::env::temp_dir().join("vaint_raster_export_test.png");
+
+        super::export(&shapes, WHITE, &Camera::default(), (20, 20), &path).expect("No se pudo exportar la imagen");
+        let exported = image::open(&path).expect("No se pudo leer la imagen exportada").to_rgb8();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(exported, render(&shapes, WHITE, (20, 20)));
+    }
+
+    #[test]
+    fn anti_aliasing_blends_outline() {
+        let mut line = ShapeObject::new_line((2, 2), (17, 7), LineAlgorithm::Bresenham);
+        *line.style_mut() = line.style_mut().stroke_color(Color::BLACK).anti_aliasing(true);
+        let image = render(&vec![line], WHITE, (20, 10));
+
+        assert_eq!(*image.get_pixel(2, 2), Rgb(Color::BLACK.0), "Los extremos tienen cobertura completa");
+        assert_eq!(*image.get_pixel(17, 7), Rgb(Color::BLACK.0), "Los extremos tienen cobertura completa");
+        // En x = 3 la línea ideal pasa por y = 2.33
+        let [above, below] = [image.get_pixel(3, 2).0[0], image.get_pixel(3, 3).0[0]];
+        assert!(above < below && below < 255, "Se esperaba una mezcla gradual: {above}, {below}");
+        assert!(above > 0, "Se esperaba una mezcla gradual: {above}, {below}");
+    }
+
+    #[test]
+    fn ignores_points_off_canvas() {
+        let circle = ShapeObject::new_circle(30, (0, 0));
+        let image = render(&vec![circle], WHITE, (20, 20));
+        assert_eq!(*image.get_pixel(0, 0), Rgb(WHITE.0));
+    }
+}
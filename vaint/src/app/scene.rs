@@ -0,0 +1,493 @@
+//! Documento de escena: las figuras a dibujar, cada una con su geometría, transformación y estilo,
+//! junto con el tamaño del lienzo y el color de fondo.
+//!
+//! Las escenas se guardan como JSON con un campo `version`. Los archivos sin ese campo se leen con
+//! el formato antiguo de [`Config`], que tiene una sola figura de cada tipo.
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::{FillRule, LineAlgorithm};
+use crate::geometries::{ArcKind, Transform};
+use crate::{Color, Config, Figura, GlShapeList, PixelCoord, ShapeObject, ShapeStyle};
+
+/// Versión del formato de escena que escribe esta versión de vaint.
+pub const SCENE_VERSION: u32 = 1;
+
+/// Ruta de la escena que comparten el panel de control y la ventana de OpenGL.
+pub const SCENE_PATH: &str = "scene.json";
+
+/// Ruta de la configuración en el formato antiguo, que se lee si no existe [`SCENE_PATH`].
+pub const LEGACY_CONFIG_PATH: &str = "config.json";
+
+/// Escena completa: lienzo, fondo y figuras, en el orden en que se dibujan.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Scene {
+    /// Versión del formato con el que se escribió la escena (ver [`SCENE_VERSION`]).
+    pub version: u32,
+    /// Tamaño del lienzo, en pixeles.
+    pub canvas_size: (u32, u32),
+    pub background_color: Color,
+    #[serde(default)]
+    pub shapes: Vec<SceneShape>,
+}
+
+/// Figura de una escena.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SceneShape {
+    #[serde(flatten)]
+    pub geometry: Geometry,
+    /// Transformación de la figura respecto a su centro (ver [`ShapeObject::transform`]).
+    #[serde(default)]
+    pub transform: Transform,
+    #[serde(default)]
+    pub style: ShapeStyle,
+}
+
+/// Tipo y dimensiones de una figura de la escena. Cada variante corresponde a uno de los
+/// constructores de [`ShapeObject`], y se identifica en JSON por el campo `kind`.
+///
+/// Los ángulos están en radianes.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Geometry {
+    Square {
+        side: u32,
+        center: PixelCoord,
+    },
+    Rectangle {
+        width: u32,
+        height: u32,
+        center: PixelCoord,
+    },
+    RoundedRect {
+        width: u32,
+        height: u32,
+        radius: u32,
+        center: PixelCoord,
+    },
+    Circle {
+        radius: u32,
+        center: PixelCoord,
+    },
+    Ellipse {
+        radius_x: u32,
+        radius_y: u32,
+        center: PixelCoord,
+    },
+    Arc {
+        radius_x: u32,
+        radius_y: u32,
+        start: f32,
+        end: f32,
+        #[serde(default)]
+        arc_kind: ArcKind,
+        center: PixelCoord,
+    },
+    Line {
+        start: PixelCoord,
+        end: PixelCoord,
+        #[serde(default)]
+        algorithm: LineAlgorithm,
+    },
+    Polyline {
+        points: Vec<PixelCoord>,
+        #[serde(default)]
+        algorithm: LineAlgorithm,
+    },
+    Bezier {
+        control_points: Vec<PixelCoord>,
+        #[serde(default)]
+        algorithm: LineAlgorithm,
+    },
+    Polygon {
+        vertices: Vec<PixelCoord>,
+        #[serde(default)]
+        fill_rule: FillRule,
+    },
+    RegularPolygon {
+        sides: u32,
+        radius: u32,
+        center: PixelCoord,
+        #[serde(default)]
+        rotation: f32,
+    },
+    Star {
+        points: u32,
+        outer_radius: u32,
+        inner_radius: u32,
+        center: PixelCoord,
+    },
+    /// Región de pixeles, como las que crea la cubeta. Se rellena con el color de relleno del
+    /// estilo de la figura.
+    Region {
+        points: Vec<PixelCoord>,
+    },
+}
+
+/// Error al leer o escribir una escena.
+#[derive(Debug)]
+pub enum SceneError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// La escena se escribió con una versión del formato que esta versión de vaint no conoce.
+    UnsupportedVersion(u32),
+}
+
+impl Scene {
+    /// Crea una escena vacía con el lienzo y el fondo dados.
+    pub fn new(canvas_size: (u32, u32), background_color: Color) -> Self {
+        Self { version: SCENE_VERSION, canvas_size, background_color, shapes: Vec::new() }
+    }
+
+    /// Lee una escena en JSON. Si el JSON no tiene el campo `version`, se lee con el formato
+    /// antiguo de [`Config`] y se convierte con [`Scene::from_config`].
+    pub fn from_json(json: &str) -> Result<Self, SceneError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if value.get("version").is_none() {
+            let config: Config = serde_json::from_value(value)?;
+            return Ok(Self::from_config(&config));
+        }
+
+        let scene: Self = serde_json::from_value(value)?;
+        match scene.version {
+            1..=SCENE_VERSION => Ok(scene),
+            version => Err(SceneError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Escribe la escena en JSON.
+    pub fn to_json(&self) -> String { serde_json::to_string_pretty(self).expect("Las escenas siempre se pueden serializar") }
+
+    /// Lee la escena guardada en la ruta dada (ver [`Scene::from_json`]).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SceneError> { Self::from_json(&std::fs::read_to_string(path)?) }
+
+    /// Lee la escena de [`SCENE_PATH`], o la configuración antigua de [`LEGACY_CONFIG_PATH`] si la
+    /// escena no existe.
+    pub fn load_default() -> Result<Self, SceneError> { Self::load(Self::default_path()) }
+
+    /// Ruta que lee [`Scene::load_default`]: [`SCENE_PATH`] si existe, o si no
+    /// [`LEGACY_CONFIG_PATH`].
+    pub fn default_path() -> &'static str {
+        match Path::new(SCENE_PATH).exists() {
+            true => SCENE_PATH,
+            false => LEGACY_CONFIG_PATH,
+        }
+    }
+
+    /// Guarda la escena en la ruta dada.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SceneError> { Ok(std::fs::write(path, self.to_json())?) }
+
+    /// Convierte la configuración del formato antiguo en una escena. Todas las figuras comparten el
+    /// estilo de la configuración, y el lienzo es del tamaño de la ventana de OpenGL (800x600).
+    pub fn from_config(config: &Config) -> Self {
+        let style = ShapeStyle::default()
+            .stroke_color(config.stroke_color)
+            .fill_color(config.shape_background_color)
+            .stroke_width(config.grosor)
+            .anti_aliasing(config.antialiasing)
+            .line_join(config.union_linea);
+
+        let shapes = config
+            .figuras
+            .iter()
+            .map(|figura| {
+                let geometry = match figura {
+                    Figura::Circulo => Geometry::Circle { radius: config.radio_circulo, center: config.centro_circulo },
+                    Figura::Cuadrado => Geometry::Square { side: config.cuadrado, center: config.centro_cuadrado },
+                    Figura::Rectangulo => {
+                        Geometry::Rectangle {
+                            width: config.ancho_rectangulo,
+                            height: config.largo_rectangulo,
+                            center: config.centro_rectangulo,
+                        }
+                    }
+                    Figura::Elipse => {
+                        Geometry::Ellipse {
+                            radius_x: config.radio1_elipse,
+                            radius_y: config.radio2_elipse,
+                            center: config.centro_elipse,
+                        }
+                    }
+                    Figura::Linea => {
+                        Geometry::Line { start: config.inicio_linea, end: config.fin_linea, algorithm: config.algoritmo_linea }
+                    }
+                    Figura::Polilinea => {
+                        Geometry::Polyline { points: config.puntos_polilinea.clone(), algorithm: config.algoritmo_linea }
+                    }
+                    Figura::RectanguloRedondeado => {
+                        Geometry::RoundedRect {
+                            width: config.ancho_rect_redondeado,
+                            height: config.largo_rect_redondeado,
+                            radius: config.radio_rect_redondeado,
+                            center: config.centro_rect_redondeado,
+                        }
+                    }
+                    Figura::PoligonoRegular => {
+                        Geometry::RegularPolygon {
+                            sides: config.lados_poligono,
+                            radius: config.radio_poligono,
+                            center: config.centro_poligono,
+                            rotation: config.rotacion_poligono.to_radians(),
+                        }
+                    }
+                    Figura::Estrella => {
+                        Geometry::Star {
+                            points: config.puntas_estrella,
+                            outer_radius: config.radio_exterior_estrella,
+                            inner_radius: config.radio_interior_estrella,
+                            center: config.centro_estrella,
+                        }
+                    }
+                };
+                SceneShape::new(geometry, style)
+            })
+            .collect();
+
+        Self { shapes, ..Self::new((800, 600), config.background_color) }
+    }
+
+    /// Construye las figuras de la escena, listas para dibujar.
+    pub fn to_shapes(&self) -> GlShapeList { self.shapes.iter().map(SceneShape::to_shape_object).collect() }
+}
+
+impl SceneShape {
+    /// Crea una figura sin transformación con la geometría y el estilo dados.
+    pub fn new(geometry: Geometry, style: ShapeStyle) -> Self { Self { geometry, transform: Transform::IDENTITY, style } }
+
+    /// Construye la figura con su transformación y su estilo.
+    pub fn to_shape_object(&self) -> ShapeObject {
+        let mut shape = match &self.geometry {
+            &Geometry::Square { side, center } => ShapeObject::new_square(side, center),
+            &Geometry::Rectangle { width, height, center } => ShapeObject::new_rectangle(width, height, center),
+            &Geometry::RoundedRect { width, height, radius, center } => {
+                ShapeObject::new_rounded_rect(width, height, radius, center)
+            }
+            &Geometry::Circle { radius, center } => ShapeObject::new_circle(radius, center),
+            &Geometry::Ellipse { radius_x, radius_y, center } => ShapeObject::new_ellipse(radius_x, radius_y, center),
+            &Geometry::Arc { radius_x, radius_y, start, end, arc_kind, center } => {
+                match arc_kind {
+                    ArcKind::Open => ShapeObject::new_arc(radius_x, radius_y, start, end, center),
+                    ArcKind::Pie => ShapeObject::new_pie(radius_x, radius_y, start, end, center),
+                    ArcKind::Chord => ShapeObject::new_chord(radius_x, radius_y, start, end, center),
+                }
+            }
+            &Geometry::Line { start, end, algorithm } => ShapeObject::new_line(start, end, algorithm),
+            Geometry::Polyline { points, algorithm } => ShapeObject::new_polyline(points, *algorithm),
+            Geometry::Bezier { control_points, algorithm } => ShapeObject::new_bezier(control_points, *algorithm),
+            Geometry::Polygon { vertices, fill_rule } => ShapeObject::new_polygon(vertices, *fill_rule),
+            &Geometry::RegularPolygon { sides, radius, center, rotation } => {
+                ShapeObject::new_regular_polygon(sides, radius, center, rotation)
+            }
+            &Geometry::Star { points, outer_radius, inner_radius, center } => {
+                ShapeObject::new_star(points, outer_radius, inner_radius, center)
+            }
+            Geometry::Region { points } => ShapeObject::new_region(points, self.style.fill_color.unwrap_or(Color::BLACK)),
+        };
+        *shape.style_mut() = self.style;
+        shape.transform(self.transform)
+    }
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(e) => write!(f, "No se pudo acceder al archivo de la escena: {e}"),
+            SceneError::Json(e) => write!(f, "La escena no es válida: {e}"),
+            SceneError::UnsupportedVersion(version) => {
+                write!(f, "La versión {version} del formato de escena no es compatible (se admite hasta la {SCENE_VERSION})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SceneError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SceneError::Io(e) => Some(e),
+            SceneError::Json(e) => Some(e),
+            SceneError::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SceneError {
+    fn from(value: std::io::Error) -> Self { SceneError::Io(value) }
+}
+
+impl From<serde_json::Error> for SceneError {
+    fn from(value: serde_json::Error) -> Self { SceneError::Json(value) }
+}
+
+/// Revisa si el archivo de una escena cambió, según su fecha de modificación, para volver a
+/// leerlo.
+pub struct SceneWatcher {
+    path: PathBuf,
+    /// Fecha de modificación del archivo en la última revisión, o `None` si no existía.
+    modified: Option<SystemTime>,
+}
+
+impl SceneWatcher {
+    /// Tiempo entre revisiones de [`SceneWatcher::watch`].
+    pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    /// Vigila el archivo dado. La versión actual del archivo no cuenta como un cambio.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let modified = modified(&path);
+        Self { path, modified }
+    }
+
+    /// Vuelve a leer la escena si el archivo cambió desde la última revisión. Devuelve `None` si no
+    /// cambió, o si ya no existe.
+    pub fn poll(&mut self) -> Option<Result<Scene, SceneError>> {
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(Scene::load(&self.path))
+    }
+
+    /// Revisa el archivo cada [`SceneWatcher::POLL_INTERVAL`], y llama a `handle` con cada lectura,
+    /// hasta que devuelva `false`. Bloquea el hilo actual.
+    ///
+    /// Un error de lectura no detiene la revisión: un editor de texto puede guardar el archivo a
+    /// medias, y el siguiente cambio lo vuelve a leer.
+    pub fn watch(mut self, mut handle: impl FnMut(Result<Scene, SceneError>) -> bool) {
+        loop {
+            std::thread::sleep(Self::POLL_INTERVAL);
+            if self.poll().is_some_and(|result| !handle(result)) {
+                return;
+            }
+        }
+    }
+}
+
+/// Fecha de modificación del archivo, o `None` si no existe.
+fn modified(path: &Path) -> Option<SystemTime> { std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok() }
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    use super::{Geometry, SCENE_VERSION, Scene, SceneError, SceneShape, SceneWatcher};
+    use crate::algorithms::FillRule;
+    use crate::geometries::{ArcKind, Transform};
+    use crate::{Color, Shape, ShapeStyle};
+
+    const RED: Color = Color::from_u32_rgb(0xFF0000);
+
+    #[test]
+    fn round_trip() {
+        let style = ShapeStyle::default().stroke_color(RED).stroke_width(3.0);
+        let mut scene = Scene::new((320, 240), Color::from_u32_rgb(0xFFFFFF));
+        scene.shapes = vec![
+            SceneShape::new(Geometry::Circle { radius: 10, center: (50, 50) }, style),
+            SceneShape {
+                transform: Transform::rotate(0.5),
+                ..SceneShape::new(Geometry::Rectangle { width: 20, height: 10, center: (100, 80) }, ShapeStyle::default())
+            },
+            SceneShape::new(
+                Geometry::Arc { radius_x: 20, radius_y: 10, start: 0.0, end: 2.0, arc_kind: ArcKind::Pie, center: (0, 0) },
+                style,
+            ),
+            SceneShape::new(Geometry::Polygon { vertices: vec![(0, 0), (10, 0), (5, 8)], fill_rule: FillRule::NonZero }, style),
+            SceneShape::new(Geometry::Region { points: vec![(1, 1), (1, 2)] }, style.fill_color(RED)),
+        ];
+
+        let json = scene.to_json();
+        assert!(json.contains(r#""version": 1"#) && json.contains(r#""kind": "circle""#));
+        assert_eq!(Scene::from_json(&json).unwrap(), scene);
+
+        let shapes = scene.to_shapes();
+        assert_eq!(shapes.len(), 5);
+        assert_eq!(shapes[0].style().stroke_width, 3.0);
+        assert_eq!(shapes[1].transform, Transform::rotate(0.5));
+        assert_eq!(shapes[4].style().fill_color, Some(RED));
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let json = r#"{
+            "version": 1,
+            "canvas_size": [100, 100],
+            "background_color": [255, 255, 255],
+            "shapes": [{ "kind": "line", "start": [0, 0], "end": [10, 5] }]
+        }"#;
+        let scene = Scene::from_json(json).unwrap();
+        assert_eq!(scene.shapes[0].style, ShapeStyle::default());
+        assert_eq!(scene.shapes[0].transform, Transform::IDENTITY);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let json = format!(r#"{{ "version": {}, "canvas_size": [1, 1], "background_color": [0, 0, 0] }}"#, SCENE_VERSION + 1);
+        assert!(matches!(Scene::from_json(&json), Err(SceneError::UnsupportedVersion(v)) if v == SCENE_VERSION + 1));
+        assert!(matches!(Scene::from_json("{ \"version\": 1 }"), Err(SceneError::Json(_))));
+    }
+
+    #[test]
+    fn loads_legacy_config() {
+        let json = r#"{
+            "stroke_color": [255, 0, 0],
+            "shape_background_color": [0, 0, 255],
+            "background_color": [255, 255, 255],
+            "figuras": ["Circulo", "Cuadrado", "PoligonoRegular"],
+            "grosor": 2.0,
+            "cuadrado": 50, "centro_cuadrado": [300, 300],
+            "largo_rectangulo": 60, "ancho_rectangulo": 30, "centro_rectangulo": [300, 300],
+            "radio1_elipse": 20, "radio2_elipse": 30, "centro_elipse": [300, 300],
+            "radio_circulo": 40, "centro_circulo": [200, 150],
+            "lados_poligono": 6, "radio_poligono": 50, "centro_poligono": [100, 100], "rotacion_poligono": 90.0
+        }"#;
+        let scene = Scene::from_json(json).unwrap();
+
+        assert_eq!(scene.version, SCENE_VERSION);
+        assert_eq!(scene.canvas_size, (800, 600));
+        assert_eq!(scene.background_color, Color::from_u32_rgb(0xFFFFFF));
+        assert_eq!(scene.shapes.len(), 3);
+        assert_eq!(scene.shapes[0].geometry, Geometry::Circle { radius: 40, center: (200, 150) });
+        assert_eq!(scene.shapes[1].geometry, Geometry::Square { side: 50, center: (300, 300) });
+        assert!(
+            matches!(scene.shapes[2].geometry, Geometry::RegularPolygon { rotation, .. } if (rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-6),
+            "La rotación se convierte de grados a radianes"
+        );
+
+        let style = scene.shapes[0].style;
+        assert_eq!((style.stroke_color, style.fill_color, style.stroke_width), (Some(RED), Some(Color::from_u32_rgb(0xFF)), 2.0));
+        assert!(scene.shapes.iter().all(|shape| shape.style == style));
+    }
+
+    #[test]
+    fn watcher_reloads_changed_file() {
+        let path = std::env::temp_dir().join("vaint_scene_watcher_test.json");
+        let _ = std::fs::remove_file(&path);
+        let mut watcher = SceneWatcher::new(&path);
+        assert!(watcher.poll().is_none(), "El archivo no existe");
+
+        // Se fija la fecha de modificación, ya que dos escrituras seguidas pueden tener la misma
+        let write = |contents: &str, seconds: u64| {
+            std::fs::write(&path, contents).unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+                .unwrap();
+        };
+        let scene = Scene::new((10, 10), RED);
+        write(&scene.to_json(), 1);
+        assert_eq!(watcher.poll().unwrap().unwrap(), scene);
+        assert!(watcher.poll().is_none(), "El archivo no cambió");
+
+        write("{", 2);
+        assert!(matches!(watcher.poll(), Some(Err(SceneError::Json(_)))));
+        write(&scene.to_json(), 3);
+        assert_eq!(watcher.poll().unwrap().unwrap(), scene);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+//! Vista de la escena, compartida por la ventana de OpenGL y el lienzo del panel de control:
+//! desplazamiento y acercamiento de la cámara, y la caja que encuadra a las figuras.
+use crate::algorithms::ClipRect;
+use crate::{PixelCoord, Shape, ShapeObject};
+
+/// Vista de la escena en la ventana.
+///
+/// Un punto `p` de la escena se ve en la posición `(p - offset) · zoom` de la ventana, en pixeles.
+/// Con un acercamiento mayor a 1, cada pixel de la escena cubre varios pixeles de la ventana.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Punto de la escena que se ve en la esquina superior izquierda de la ventana.
+    pub offset: (f32, f32),
+    /// Pixeles de la ventana por cada pixel de la escena.
+    pub zoom: f32,
+}
+
+impl Camera {
+    /// Acercamiento máximo permitido.
+    pub const MAX_ZOOM: f32 = 64.0;
+    /// Acercamiento mínimo permitido.
+    pub const MIN_ZOOM: f32 = 1.0 / 16.0;
+
+    /// Convierte una posición de la ventana en un punto de la escena.
+    pub fn screen_to_world(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (x / self.zoom + self.offset.0, y / self.zoom + self.offset.1)
+    }
+
+    /// Convierte un punto de la escena en una posición de la ventana.
+    pub fn world_to_screen(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        ((x - self.offset.0) * self.zoom, (y - self.offset.1) * self.zoom)
+    }
+
+    /// Pixel de la escena que se ve en el pixel dado de la ventana.
+    pub fn screen_to_world_pixel(&self, (x, y): PixelCoord) -> PixelCoord {
+        // Se usa el centro del pixel de la ventana
+        let (x, y) = self.screen_to_world((x as f32 + 0.5, y as f32 + 0.5));
+        (x.floor() as i32, y.floor() as i32)
+    }
+
+    /// Pixeles de la escena visibles en una ventana de las dimensiones dadas.
+    pub fn visible_rect(&self, (width, height): (u32, u32)) -> ClipRect {
+        let (x_min, y_min) = self.offset;
+        let (x_max, y_max) = self.screen_to_world((width as f32, height as f32));
+        ClipRect::new((x_min.floor() as i32, y_min.floor() as i32), (x_max.ceil() as i32 - 1, y_max.ceil() as i32 - 1))
+    }
+
+    /// Multiplica el acercamiento por `factor`, manteniendo fijo el punto de la escena que se ve en
+    /// la posición `anchor` de la ventana.
+    pub fn zoom_at(&mut self, anchor: (f32, f32), factor: f32) {
+        let (x, y) = self.screen_to_world(anchor);
+        self.zoom = (self.zoom * factor).clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+        self.offset = (x - anchor.0 / self.zoom, y - anchor.1 / self.zoom);
+    }
+
+    /// Desplaza la vista para que la escena siga al cursor, que se movió `delta` pixeles de la
+    /// ventana.
+    pub fn pan(&mut self, (dx, dy): (f32, f32)) {
+        self.offset = (self.offset.0 - dx / self.zoom, self.offset.1 - dy / self.zoom);
+    }
+
+    /// Ajusta la vista para que el rectángulo dado quepa centrado en una ventana de las dimensiones
+    /// dadas, dejando `margin` pixeles de la ventana libres en cada lado.
+    pub fn fit(&mut self, rect: &ClipRect, (width, height): (u32, u32), margin: f32) {
+        let rect_width = (rect.x_max - rect.x_min + 1) as f32;
+        let rect_height = (rect.y_max - rect.y_min + 1) as f32;
+        let available = ((width as f32 - 2.0 * margin).max(1.0), (height as f32 - 2.0 * margin).max(1.0));
+        self.zoom = (available.0 / rect_width).min(available.1 / rect_height).clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+
+        let center = (rect.x_min as f32 + rect_width / 2.0, rect.y_min as f32 + rect_height / 2.0);
+        self.offset = (center.0 - width as f32 / 2.0 / self.zoom, center.1 - height as f32 / 2.0 / self.zoom);
+    }
+
+    /// Matriz de la vista para el vertex shader, por columnas. Lleva un punto de la escena a su
+    /// posición en la ventana, en pixeles.
+    pub fn matrix(&self) -> [[f32; 4]; 4] {
+        let (x, y) = self.offset;
+        [[self.zoom, 0.0, 0.0, 0.0], [0.0, self.zoom, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [-x * self.zoom, -y * self.zoom, 0.0, 1.0]]
+    }
+}
+
+impl Default for Camera {
+    /// Vista sin desplazamiento ni acercamiento: cada pixel de la escena es un pixel de la ventana.
+    fn default() -> Self { Self { offset: (0.0, 0.0), zoom: 1.0 } }
+}
+
+/// Caja que contiene a todas las figuras de la lista, o `None` si ninguna tiene pixeles.
+pub fn shapes_bounds(shapes_list: &[ShapeObject]) -> Option<ClipRect> {
+    shapes_list
+        .iter()
+        .filter_map(ShapeObject::bounding_box)
+        .reduce(|a, b| ClipRect::new((a.x_min.min(b.x_min), a.y_min.min(b.y_min)), (a.x_max.max(b.x_max), a.y_max.max(b.y_max))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Camera;
+    use crate::algorithms::ClipRect;
+
+    #[track_caller]
+    fn assert_close(a: (f32, f32), b: (f32, f32)) {
+        assert!((a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn screen_world_round_trip() {
+        let camera = Camera { offset: (10.0, -5.0), zoom: 4.0 };
+        assert_close(camera.world_to_screen(camera.screen_to_world((13.0, 27.0))), (13.0, 27.0));
+        assert_close(camera.screen_to_world((0.0, 0.0)), (10.0, -5.0));
+        // Cada pixel de la escena cubre 4x4 pixeles de la ventana
+        assert_eq!(camera.screen_to_world_pixel((0, 0)), (10, -5));
+        assert_eq!(camera.screen_to_world_pixel((3, 3)), (10, -5));
+        assert_eq!(camera.screen_to_world_pixel((4, 8)), (11, -3));
+        assert_eq!(camera.visible_rect((40, 20)), ClipRect::new((10, -5), (19, -1)));
+        assert_eq!(Camera::default().visible_rect((800, 600)), ClipRect::from_dimensions((800, 600)));
+    }
+
+    #[test]
+    fn zoom_keeps_anchor_fixed() {
+        let mut camera = Camera { offset: (3.0, 7.0), zoom: 1.5 };
+        let anchor = (120.0, 45.0);
+        let before = camera.screen_to_world(anchor);
+        camera.zoom_at(anchor, 2.5);
+        assert_close(camera.screen_to_world(anchor), before);
+
+        camera.zoom_at(anchor, 1e6);
+        assert_eq!(camera.zoom, Camera::MAX_ZOOM);
+        assert_close(camera.screen_to_world(anchor), before);
+
+        camera.pan((8.0, -16.0));
+        assert_close(camera.screen_to_world((anchor.0 + 8.0, anchor.1 - 16.0)), before);
+    }
+
+    #[test]
+    fn fit_centers_rect() {
+        let mut camera = Camera::default();
+        let rect = ClipRect::new((100, 50), (199, 99));
+        camera.fit(&rect, (400, 400), 0.0);
+
+        assert_eq!(camera.zoom, 4.0);
+        assert_close(camera.world_to_screen((100.0, 75.0)), (0.0, 200.0));
+        assert_close(camera.world_to_screen((200.0, 75.0)), (400.0, 200.0));
+    }
+}
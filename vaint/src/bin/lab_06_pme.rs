@@ -1,6 +1,5 @@
 #![allow(dead_code)]
 use core::f32;
-use std::mem;
 
 use glium::index::{NoIndices, PrimitiveType};
 use glium::winit::application::ApplicationHandler;
@@ -159,83 +158,9 @@ fn elipse_punto_medio(centro: Point, rx: i32, ry: i32) -> Vec<Point> {
 
 /// Algoritmo de Bresenham para dibujar líneas sin usar multiplicación de flotantes.
 fn bresenham(p0: Point, p1: Point) -> Vec<Point> {
-    tracing::trace!("Bresenhan: ({x0}:{y0}) ({x1},{y1})", x0 = p0.0, y0 = p0.1, x1 = p1.0, y1 = p1.1);
-    let (delta_x, delta_y) = ((p1.0 - p0.0), p1.1 - p0.1);
-    tracing::trace!("Delta: (|{delta_x}| >= |{delta_y}|)", delta_x = delta_x, delta_y = delta_y);
-    // Usamos la implementación que nos dará la mayor cantidad de pasos en renderizado
-    if (delta_y).abs() <= (delta_x).abs() { h_bressenham(p0, p1) } else { v_bressenham(p1, p0) }
-}
-
-fn v_bressenham((mut x0, mut y0): Point, (mut x1, mut y1): Point) -> Vec<Point> {
-    if y1 < y0 {
-        mem::swap(&mut y0, &mut y1);
-        mem::swap(&mut x0, &mut x1);
-    }
-
-    let delta_y: i32 = y1 - y0;
-    let delta_x: i32 = x1 - x0;
-
-    let (x0, y0, _x1, y1, delta_x, delta_y, x_increment): (i32, i32, i32, i32, i32, i32, i32) =
-        (x0, y0, x1, y1, delta_x.abs(), delta_y.abs(), if x0 > x1 { -1 } else { 1 });
-
-    let mut p: i32 = 2 * delta_x - delta_y;
-    let two_dx: i32 = 2 * delta_x;
-    let two_dx_dy: i32 = 2 * (delta_x - delta_y);
-
-    let mut points: Vec<Point> = Vec::new();
-
-    // Coordenadas inicial de la línea
-
-    // let mut y: i32 = i32::min(y0, y1);
-    let mut x = x0;
-    let y1: i32 = i32::max(y0, y1);
-
-    points.push((x, y0));
-    for y in (y0 + 1)..=y1 {
-        if p < 0 {
-            p += two_dx;
-        } else {
-            x += x_increment;
-            p += two_dx_dy;
-        }
-        points.push((x, y));
-    }
-    points
-}
-
-fn h_bressenham((mut x0, mut y0): Point, (mut x1, mut y1): Point) -> Vec<Point> {
-    if x1 < x0 {
-        mem::swap(&mut x0, &mut x1);
-        mem::swap(&mut y0, &mut y1);
-    }
-    let delta_x: i32 = x1 - x0;
-    let delta_y: i32 = y1 - y0;
-
-    let (x0, y0, x1, _y1, delta_x, delta_y, y_increment) =
-        (x0, y0, x1, y1, delta_x.abs(), delta_y.abs(), if y0 < y1 { 1 } else { -1 });
-
-    let mut p: i32 = 2 * delta_y - delta_x;
-    let two_dy: i32 = 2 * delta_y;
-    let two_dy_dx: i32 = 2 * (delta_y - delta_x);
-
-    let mut points: Vec<Point> = Vec::new();
-
-    // Coordenadas inicial de la línea
-
-    // let mut y: i32 = i32::min(y0, y1);
-    let mut y = y0;
-    let x1: i32 = i32::max(x0, x1);
-    points.push((x0, y));
-    for x in (x0 + 1)..=x1 {
-        if p < 0 {
-            p += two_dy;
-        } else {
-            y += y_increment;
-            p += two_dy_dx;
-        }
-        points.push((x, y));
-    }
-    points
+    let mut puntos = Vec::new();
+    vaint::algorithms::write_bresenham(p0, p1, &mut puntos);
+    puntos
 }
 
 pub fn dda((x_0, y_0): (i32, i32), (x, y): (i32, i32)) -> Vec<Point> {
@@ -375,203 +300,3 @@ impl Vertex {
 
     pub fn new_blue(position: [i32; 2]) -> Self { Vertex::new(position, [0.0, 0.0, 1.0]) }
 }
-
-/// Codigo de Prueba para el test de bressenham
-#[cfg(test)]
-mod test {
-    use core::panic;
-    use std::sync::LazyLock;
-
-    use crate::{Point, bresenham, dda};
-
-    static TRACING: LazyLock<()> = LazyLock::new(|| {
-        let _ = tracing_subscriber::fmt().without_time().with_file(true).with_line_number(true).try_init().ok();
-    });
-
-    #[test]
-    fn bress_360deg() {
-        *TRACING;
-        let p0 = (10, 0);
-        let p1 = (219, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_15deg() {
-        *TRACING;
-        let p0 = (10, 3);
-        let p1 = (208, 56);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_45deg() {
-        *TRACING;
-        let p0 = (10, 0);
-        let p1 = (210, 200);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_60deg() {
-        *TRACING;
-        let p0 = (0, -173);
-        let p1 = (100, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_90deg() {
-        *TRACING;
-        let p0 = (0, 0);
-        let p1 = (0, -200);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_120deg() {
-        *TRACING;
-        let p0 = (100, 0);
-        let p1 = (0, -173);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_165deg() {
-        *TRACING;
-        let p0 = (208, 3);
-        let p1 = (10, 56);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_180deg() {
-        *TRACING;
-        let p0 = (219, 555);
-        let p1 = (10, 555);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_195deg() {
-        *TRACING;
-        let p0 = (208, 3);
-        let p1 = (10, 56);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_225deg() {
-        *TRACING;
-        let p0 = (210, 200);
-        let p1 = (10, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_240deg() {
-        *TRACING;
-        let p0 = (100, 173);
-        let p1 = (0, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_270deg() {
-        *TRACING;
-        let p0 = (0, 200);
-        let p1 = (0, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_300deg() {
-        *TRACING;
-        let p0 = (0, 173);
-        let p1 = (100, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_315deg() {
-        *TRACING;
-        let p0 = (10, 200);
-        let p1 = (210, 0);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-
-    #[test]
-    fn bress_345deg() {
-        *TRACING;
-        let p0 = (10, 56);
-        let p1 = (208, 3);
-        let b_points = bresenham(p0, p1);
-        let d_points = dda(p0, p1);
-        compare(b_points, d_points);
-    }
-    #[track_caller]
-    fn compare(mut a_points: Vec<Point>, mut b_points: Vec<Point>) {
-        let (b, a) = (b_points.last().unwrap(), b_points.first().unwrap());
-        let (x, y) = (b.0 - a.0, b.1 - a.1);
-        tracing::debug!(
-            "Componentes: x:{x:?} y:{y:?}. Angle: {alpha:.2} deg",
-            alpha = f64::atan(y as f64 / x as f64).to_degrees()
-        );
-        a_points.sort();
-        b_points.sort();
-
-        // a_points.sort_by_key(|&(a, b)| (a));
-        // b_points.sort_by_key(|&(a, b)| (a));
-        assert_eq!(a_points.len(), b_points.len(), "Los dos arreglos no tienen la misma longitud");
-        let longest = a_points.len().max(b_points.len());
-        let mut errors = 0;
-        let mut report = Vec::new();
-        for idx in 0..longest {
-            let i = a_points.get(idx);
-            let j = b_points.get(idx);
-            if i != j {
-                report.push(Err(format!("Se esperaba que los puntos fueran iguales: {:?} != {:?}", i, j)));
-                errors += 1;
-            } else {
-                report.push(Ok(format!("Los puntos son iguales: {:?} ", j)));
-            }
-        }
-        if errors > longest * 5 / 100 {
-            for r in report.into_iter() {
-                match r {
-                    Ok(ok) => tracing::info!("{}", ok),
-                    Err(err) => tracing::error!("{}", err),
-                }
-            }
-            panic!("Los puntos no son iguales");
-        }
-    }
-}
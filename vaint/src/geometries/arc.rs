@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use super::{DrawableShape, Polygon, Polyline, Shape, ShapeStyle, Transform};
+use crate::PixelCoord;
+use crate::algorithms::{
+    CoveragePoint, LineAlgorithm, ellipse_point_at, write_ellipse_arc_middle_point, write_ellipse_chord_fill,
+    write_ellipse_sector_fill, write_wu_ellipse_arc, write_wu_line,
+};
+
+/// Forma de cerrar un [`Arc`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ArcKind {
+    /// Arco abierto, sin relleno.
+    #[default]
+    Open,
+    /// Sector (rebanada de pastel): los extremos del arco se unen con el centro.
+    Pie,
+    /// Segmento: los extremos del arco se unen entre sí con una cuerda.
+    Chord,
+}
+
+/// Arco de elipse (o de círculo, si los radios son iguales) centrado en el centro del objeto, que
+/// va del ángulo `start` al ángulo `end`.
+///
+/// Los ángulos están en radianes y crecen en sentido antihorario en pantalla (ver
+/// [`crate::algorithms::arc_contains`]).
+#[derive(Clone, Copy)]
+pub struct Arc {
+    radius_x: u32,
+    radius_y: u32,
+    start: f32,
+    end: f32,
+    kind: ArcKind,
+    pub style: ShapeStyle,
+}
+
+impl Arc {
+    /// Crea un nuevo arco abierto con los radios y ángulos dados.
+    ///
+    /// # Panics
+    ///
+    /// Entra en pánico si alguno de los radios satura el espacio de [i32].
+    pub fn new(radius_x: u32, radius_y: u32, start: f32, end: f32) -> Self {
+        assert!(radius_x < i32::MAX as u32 && radius_y < i32::MAX as u32, "Los radios del arco son demasiado grandes");
+        Self { radius_x, radius_y, start, end, kind: ArcKind::default(), style: ShapeStyle::new() }
+    }
+
+    /// Modifica la forma de cerrar el arco.
+    pub fn kind(self, kind: ArcKind) -> Self { Self { kind, ..self } }
+
+    /// Lee los campos del objeto, y los devuelve como una tupla de (radio x, radio y, ángulo
+    /// inicial, ángulo final, forma de cierre).
+    pub fn read_fields(&self) -> (u32, u32, f32, f32, ArcKind) { (self.radius_x, self.radius_y, self.start, self.end, self.kind) }
+
+    /// Modifica el estilo del arco.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada al arco. El arco se aproxima con segmentos
+    /// de recta, por lo que el resultado es una polilínea si es abierto, o un polígono si es un
+    /// sector o un segmento.
+    pub(super) fn transformed(&self, transform: &Transform) -> DrawableShape {
+        use std::f32::consts::TAU;
+
+        let (rx, ry) = (self.radius_x as i32, self.radius_y as i32);
+        let sweep = if self.is_full() { TAU } else { (self.end - self.start).rem_euclid(TAU) };
+        // Segmentos de unos 2 pixeles de largo
+        let steps = ((sweep * rx.max(ry) as f32 / 2.0).ceil() as u32).max(1);
+        let mut vertices = (0..=steps)
+            .map(|k| {
+                let (x, y) = transform.apply_linear(ellipse_point_at(rx, ry, self.start + k as f32 * sweep / steps as f32));
+                (x.round() as i32, y.round() as i32)
+            })
+            .collect::<Vec<_>>();
+        vertices.dedup();
+
+        match self.kind {
+            ArcKind::Open => Polyline::new(vertices).style(self.style).into(),
+            ArcKind::Pie if !self.is_full() => {
+                vertices.insert(0, (0, 0));
+                Polygon::new(vertices).style(self.style).into()
+            }
+            ArcKind::Pie | ArcKind::Chord => Polygon::new(vertices).style(self.style).into(),
+        }
+    }
+
+    /// Revisa si el arco da la vuelta completa, en cuyo caso no tiene lados rectos.
+    fn is_full(&self) -> bool { (self.end - self.start).abs() >= std::f32::consts::TAU }
+
+    /// Extremos ideales del arco, relativos al centro.
+    fn endpoints(&self) -> ((f32, f32), (f32, f32)) {
+        let (rx, ry) = (self.radius_x as i32, self.radius_y as i32);
+        (ellipse_point_at(rx, ry, self.start), ellipse_point_at(rx, ry, self.end))
+    }
+
+    /// Lados rectos que cierran el arco, según su [`ArcKind`].
+    fn sides(&self, center: PixelCoord) -> Vec<(PixelCoord, PixelCoord)> {
+        let (cx, cy) = center;
+        let at = |(x, y): (f32, f32)| (cx + x.round() as i32, cy + y.round() as i32);
+        let (p0, p1) = self.endpoints();
+        match self.kind {
+            _ if self.is_full() => vec![],
+            ArcKind::Open => vec![],
+            ArcKind::Pie => vec![(center, at(p0)), (center, at(p1))],
+            ArcKind::Chord => vec![(at(p0), at(p1))],
+        }
+    }
+}
+
+impl Shape for Arc {
+    /// Escribe al buffer dado los puntos del arco, seguidos de los lados que lo cierran.
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (rx, ry) = (self.radius_x as i32, self.radius_y as i32);
+        write_ellipse_arc_middle_point(center, rx, ry, self.start, self.end, buf);
+        for (p0, p1) in self.sides(center) {
+            LineAlgorithm::default().write_line(p0, p1, buf);
+        }
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        write_wu_ellipse_arc(center, self.radius_x as i32, self.radius_y as i32, self.start, self.end, buf);
+        for (p0, p1) in self.sides(center) {
+            write_wu_line(p0, p1, buf);
+        }
+    }
+
+    /// Escribe al buffer dado los puntos interiores de la elipse que quedan dentro del sector o del
+    /// lado interior de la cuerda. Un arco abierto no tiene relleno.
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (rx, ry) = (self.radius_x as i32, self.radius_y as i32);
+        match self.kind {
+            ArcKind::Open => {}
+            ArcKind::Pie => write_ellipse_sector_fill(center, rx, ry, self.start, self.end, buf),
+            ArcKind::Chord => write_ellipse_chord_fill(center, rx, ry, self.start, self.end, buf),
+        }
+    }
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
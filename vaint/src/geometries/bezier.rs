@@ -0,0 +1,82 @@
+use super::{Polyline, Shape, ShapeStyle, Transform};
+use crate::PixelCoord;
+use crate::algorithms::{BEZIER_TOLERANCE, ClipRect, CoveragePoint, LineAlgorithm, flatten_bezier};
+
+/// Curva de Bézier definida por sus puntos de control relativos al centro del objeto.
+///
+/// Con 3 puntos de control la curva es cuadrática, y con 4 es cúbica. La curva se dibuja como una
+/// [`Polyline`] que la aproxima con una distancia máxima de [`BEZIER_TOLERANCE`] (ver
+/// [`flatten_bezier`]).
+#[derive(Clone)]
+pub struct Bezier {
+    control_points: Vec<PixelCoord>,
+    algorithm: LineAlgorithm,
+    pub style: ShapeStyle,
+}
+
+impl Bezier {
+    /// Crea una nueva curva con los puntos de control dados, relativos al centro del objeto.
+    pub fn new(control_points: Vec<PixelCoord>) -> Self {
+        Self { control_points, algorithm: LineAlgorithm::default(), style: ShapeStyle::new() }
+    }
+
+    /// Crea una curva cuadrática que va de `start` a `end`, atraída por `control`.
+    pub fn quadratic(start: PixelCoord, control: PixelCoord, end: PixelCoord) -> Self { Self::new(vec![start, control, end]) }
+
+    /// Crea una curva cúbica que va de `start` a `end`, atraída por `control1` y `control2`.
+    pub fn cubic(start: PixelCoord, control1: PixelCoord, control2: PixelCoord, end: PixelCoord) -> Self {
+        Self::new(vec![start, control1, control2, end])
+    }
+
+    /// Modifica el algoritmo utilizado para trazar los segmentos que aproximan la curva.
+    pub fn algorithm(self, algorithm: LineAlgorithm) -> Self { Self { algorithm, ..self } }
+
+    /// Devuelve los puntos de control de la curva, relativos al centro del objeto.
+    pub fn control_points(&self) -> &[PixelCoord] { &self.control_points }
+
+    /// Modifica el estilo de la curva.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a los puntos de control. Como las curvas de
+    /// Bézier son invariantes ante transformaciones afines, la curva resultante es la curva
+    /// transformada.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let control_points = self.control_points.iter().map(|&point| transform.apply_linear_to_pixel(point)).collect();
+        Self { control_points, ..self.clone() }
+    }
+
+    /// Polilínea que aproxima la curva, con vértices relativos al centro del objeto.
+    pub fn to_polyline(&self) -> Polyline {
+        let control = self.control_points.iter().map(|&(x, y)| (x as f32, y as f32)).collect::<Vec<_>>();
+        let mut vertices = Vec::new();
+        flatten_bezier(&control, BEZIER_TOLERANCE, &mut vertices);
+
+        let mut points = vertices.into_iter().map(|(x, y)| (x.round() as i32, y.round() as i32)).collect::<Vec<_>>();
+        // Los segmentos más cortos que un pixel se redondean al mismo punto
+        points.dedup();
+        Polyline::new(points).algorithm(self.algorithm).style(self.style)
+    }
+}
+
+impl Shape for Bezier {
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        self.to_polyline().write_outline_points_at(buf, center);
+    }
+
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        self.to_polyline().write_clipped_outline_points_at(buf, center, clip);
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        self.to_polyline().write_aa_outline_points_at(buf, center);
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        self.to_polyline().write_stroke_points_at(buf, center);
+    }
+
+    /// Una curva de Bézier es una figura abierta, por lo que no tiene relleno.
+    fn write_fill_points_at(&self, _buf: &mut Vec<PixelCoord>, _center: PixelCoord) {}
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
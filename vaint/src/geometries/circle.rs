@@ -1,5 +1,6 @@
-use super::{Shape, ShapeStyle};
+use super::{Shape, ShapeStyle, Transform};
 use crate::PixelCoord;
+use crate::algorithms::{ClipRect, CoveragePoint};
 
 pub struct Circle {
     radius: u32,
@@ -18,6 +19,16 @@ impl Circle {
 
     /// Modifica el estilo del círculo.
     pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada al círculo, el cual se convierte en una
+    /// elipse con ejes en cualquier dirección.
+    pub fn transformed(&self, transform: &Transform) -> RotatedEllipse {
+        let radius = self.radius as f32;
+        RotatedEllipse::from_axes(transform.apply_linear((radius, 0.0)), transform.apply_linear((0.0, radius))).style(self.style)
+    }
+
+    /// El mismo círculo como una [`RotatedEllipse`], para medir distancias con su ecuación.
+    fn as_rotated(&self) -> RotatedEllipse { self.transformed(&Transform::IDENTITY) }
 }
 
 impl Shape for Circle {
@@ -32,6 +43,32 @@ impl Shape for Circle {
         crate::algorithms::write_circle_middle_point(center, self.radius as i32, buf);
     }
 
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        crate::algorithms::write_circle_middle_point_clipped(center, self.radius as i32, clip, buf);
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        crate::algorithms::write_wu_circle(center, self.radius as i32, buf);
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let radius = self.radius as i32;
+        crate::algorithms::write_thick_ellipse(center, radius, radius, self.style.stroke_width, buf);
+    }
+
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        super::hits_outline(self.as_rotated().distance(point, center), &self.style, tolerance)
+    }
+
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        self.as_rotated().contains_point_at(point, center)
+    }
+
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let radius = self.radius as i32;
+        Some(super::outline_bounds((center.0 - radius, center.1 - radius), (center.0 + radius, center.1 + radius), &self.style))
+    }
+
     fn style(&self) -> &ShapeStyle { &self.style }
 }
 
@@ -60,6 +97,17 @@ impl Ellipse {
 
     /// Modifica el estilo de la Ellipse.
     pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a la elipse, cuyos ejes dejan de estar
+    /// alineados con los de la pantalla.
+    pub fn transformed(&self, transform: &Transform) -> RotatedEllipse {
+        let (radius_x, radius_y) = (self.radius_x as f32, self.radius_y as f32);
+        RotatedEllipse::from_axes(transform.apply_linear((radius_x, 0.0)), transform.apply_linear((0.0, radius_y)))
+            .style(self.style)
+    }
+
+    /// La misma elipse como una [`RotatedEllipse`], para medir distancias con su ecuación.
+    fn as_rotated(&self) -> RotatedEllipse { self.transformed(&Transform::IDENTITY) }
 }
 
 impl Shape for Ellipse {
@@ -76,5 +124,107 @@ impl Shape for Ellipse {
         crate::algorithms::write_ellipse_middle_point(center, self.radius_x as i32, self.radius_y as i32, buf);
     }
 
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        let (radius_x, radius_y) = (self.radius_x as i32, self.radius_y as i32);
+        crate::algorithms::write_ellipse_middle_point_clipped(center, radius_x, radius_y, clip, buf);
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        crate::algorithms::write_wu_ellipse(center, self.radius_x as i32, self.radius_y as i32, buf);
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (radius_x, radius_y) = (self.radius_x as i32, self.radius_y as i32);
+        crate::algorithms::write_thick_ellipse(center, radius_x, radius_y, self.style.stroke_width, buf);
+    }
+
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        super::hits_outline(self.as_rotated().distance(point, center), &self.style, tolerance)
+    }
+
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        self.as_rotated().contains_point_at(point, center)
+    }
+
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let (radius_x, radius_y) = (self.radius_x as i32, self.radius_y as i32);
+        let (min, max) = ((center.0 - radius_x, center.1 - radius_y), (center.0 + radius_x, center.1 + radius_y));
+        Some(super::outline_bounds(min, max, &self.style))
+    }
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
+
+/// Elipse con ejes en cualquier dirección, descrita por dos semiejes conjugados (ver
+/// [`crate::algorithms::write_affine_ellipse`]).
+///
+/// Es el resultado de aplicar una [`Transform`] a un [`Circle`] o una [`Ellipse`].
+#[derive(Clone, Copy)]
+pub struct RotatedEllipse {
+    axes: [(f32, f32); 2],
+    pub style: ShapeStyle,
+}
+
+impl RotatedEllipse {
+    /// Crea una nueva elipse con los radios dados, girada `rotation` radianes en sentido
+    /// antihorario.
+    pub fn new(radius_x: f32, radius_y: f32, rotation: f32) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+        Self::from_axes((radius_x * cos, -radius_x * sin), (radius_y * sin, radius_y * cos))
+    }
+
+    /// Crea una nueva elipse formada por los puntos `u cos t + v sin t`, relativos al centro del
+    /// objeto.
+    pub fn from_axes(u: (f32, f32), v: (f32, f32)) -> Self { Self { axes: [u, v], style: ShapeStyle::new() } }
+
+    /// Devuelve los semiejes conjugados de la elipse.
+    pub fn axes(&self) -> [(f32, f32); 2] { self.axes }
+
+    /// Modifica el estilo de la elipse.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a los ejes de la elipse.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        Self { axes: self.axes.map(|axis| transform.apply_linear(axis)), ..*self }
+    }
+
+    /// Distancia del punto dado a la elipse centrada en `center`, negativa dentro de
+    /// ella (ver [`crate::algorithms::ellipse_distance`]).
+    fn distance(&self, (x, y): PixelCoord, center: PixelCoord) -> f32 {
+        crate::algorithms::ellipse_distance(self.axes, ((x - center.0) as f32, (y - center.1) as f32))
+    }
+}
+
+impl Shape for RotatedEllipse {
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        crate::algorithms::write_affine_ellipse(center, self.axes, buf);
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        crate::algorithms::write_wu_affine_ellipse(center, self.axes, buf);
+    }
+
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        crate::algorithms::write_affine_ellipse_fill(center, self.axes, buf);
+    }
+
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        super::hits_outline(self.distance(point, center), &self.style, tolerance)
+    }
+
+    /// Revisa si el punto dado es parte del relleno de la elipse. Cada fila del relleno llega hasta
+    /// el pixel más cercano al contorno, por lo que se aceptan los puntos a menos de medio pixel
+    /// fuera de la elipse.
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool { self.distance(point, center) <= 0.5 }
+
+    /// Caja delimitadora de la elipse: su extensión en cada eje es la norma de las componentes de
+    /// los semiejes en ese eje.
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let [u, v] = self.axes;
+        let (half_width, half_height) = (u.0.hypot(v.0).round() as i32, u.1.hypot(v.1).round() as i32);
+        let (min, max) = ((center.0 - half_width, center.1 - half_height), (center.0 + half_width, center.1 + half_height));
+        Some(super::outline_bounds(min, max, &self.style))
+    }
+
     fn style(&self) -> &ShapeStyle { &self.style }
 }
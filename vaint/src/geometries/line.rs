@@ -0,0 +1,165 @@
+use super::{Shape, ShapeStyle, Transform};
+use crate::PixelCoord;
+use crate::algorithms::{ClipRect, CoveragePoint, LineAlgorithm, write_thick_path, write_wu_line};
+
+/// Segmento de recta definido por dos extremos relativos al centro del objeto.
+#[derive(Clone, Copy)]
+pub struct Line {
+    start: PixelCoord,
+    end: PixelCoord,
+    algorithm: LineAlgorithm,
+    pub style: ShapeStyle,
+}
+
+impl Line {
+    /// Crea una nueva línea entre los extremos dados, relativos al centro del objeto.
+    pub fn new(start: PixelCoord, end: PixelCoord) -> Self {
+        Self { start, end, algorithm: LineAlgorithm::default(), style: ShapeStyle::new() }
+    }
+
+    /// Modifica el algoritmo utilizado para trazar la línea.
+    pub fn algorithm(self, algorithm: LineAlgorithm) -> Self { Self { algorithm, ..self } }
+
+    /// Lee los campos del objeto, y los devuelve como una tupla de (inicio, fin, algoritmo).
+    pub fn read_fields(&self) -> (PixelCoord, PixelCoord, LineAlgorithm) { (self.start, self.end, self.algorithm) }
+
+    /// Modifica el estilo de la línea.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a los extremos de la línea.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let (start, end) = (transform.apply_linear_to_pixel(self.start), transform.apply_linear_to_pixel(self.end));
+        Self { start, end, ..*self }
+    }
+}
+
+impl Shape for Line {
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (cx, cy) = center;
+        let p0 = (cx + self.start.0, cy + self.start.1);
+        let p1 = (cx + self.end.0, cy + self.end.1);
+        self.algorithm.write_line(p0, p1, buf);
+    }
+
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        let (cx, cy) = center;
+        let p0 = (cx + self.start.0, cy + self.start.1);
+        let p1 = (cx + self.end.0, cy + self.end.1);
+        self.algorithm.write_line_clipped(p0, p1, clip, buf);
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        let (cx, cy) = center;
+        write_wu_line((cx + self.start.0, cy + self.start.1), (cx + self.end.0, cy + self.end.1), buf);
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (cx, cy) = center;
+        let path = [(cx + self.start.0, cy + self.start.1), (cx + self.end.0, cy + self.end.1)];
+        write_thick_path(&path, false, self.style.stroke_width, self.style.line_join, buf);
+    }
+
+    /// Una línea es una figura abierta, por lo que no tiene relleno.
+    fn write_fill_points_at(&self, _buf: &mut Vec<PixelCoord>, _center: PixelCoord) {}
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
+
+/// Secuencia de segmentos de recta conectados, definidos por sus vértices relativos al centro del
+/// objeto.
+#[derive(Clone)]
+pub struct Polyline {
+    points: Vec<PixelCoord>,
+    algorithm: LineAlgorithm,
+    pub style: ShapeStyle,
+}
+
+impl Polyline {
+    /// Crea una nueva polilínea que une los vértices dados, relativos al centro del objeto.
+    pub fn new(points: Vec<PixelCoord>) -> Self { Self { points, algorithm: LineAlgorithm::default(), style: ShapeStyle::new() } }
+
+    /// Modifica el algoritmo utilizado para trazar los segmentos.
+    pub fn algorithm(self, algorithm: LineAlgorithm) -> Self { Self { algorithm, ..self } }
+
+    /// Devuelve los vértices de la polilínea, relativos al centro del objeto.
+    pub fn points(&self) -> &[PixelCoord] { &self.points }
+
+    /// Modifica el estilo de la polilínea.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a los vértices de la polilínea.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let points = self.points.iter().map(|&point| transform.apply_linear_to_pixel(point)).collect();
+        Self { points, ..self.clone() }
+    }
+}
+
+impl Shape for Polyline {
+    /// Escribe al buffer dado los puntos de cada segmento de la polilínea.
+    ///
+    /// # Nota
+    ///
+    /// Una polilínea con un solo vértice se dibuja como un punto.
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (cx, cy) = center;
+        let mut vertices = self.points.iter().map(|&(x, y)| (cx + x, cy + y));
+        let Some(mut previous) = vertices.next() else { return };
+
+        buf.push(previous);
+        for current in vertices {
+            let start = buf.len();
+            self.algorithm.write_line(previous, current, buf);
+            // El primer punto del segmento ya fue escrito por el segmento anterior
+            buf.remove(start);
+            previous = current;
+        }
+    }
+
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        let (cx, cy) = center;
+        let mut vertices = self.points.iter().map(|&(x, y)| (cx + x, cy + y));
+        let Some(mut previous) = vertices.next() else { return };
+
+        if clip.contains(previous) {
+            buf.push(previous);
+        }
+        for current in vertices {
+            let start = buf.len();
+            self.algorithm.write_line_clipped(previous, current, clip, buf);
+            // El primer punto del segmento ya fue escrito por el segmento anterior
+            if buf.get(start) == Some(&previous) {
+                buf.remove(start);
+            }
+            previous = current;
+        }
+    }
+
+    /// Escribe al buffer dado los puntos de cada segmento de la polilínea con su cobertura.
+    ///
+    /// # Nota
+    ///
+    /// Los vértices compartidos entre segmentos se escriben dos veces, siempre con cobertura
+    /// completa.
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        use itertools::Itertools;
+
+        let (cx, cy) = center;
+        let vertices = self.points.iter().map(|&(x, y)| (cx + x, cy + y));
+        match self.points.len() {
+            0 => {}
+            1 => buf.extend(vertices.map(|point| (point, 1.0))),
+            _ => vertices.tuple_windows().for_each(|(p0, p1)| write_wu_line(p0, p1, buf)),
+        }
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (cx, cy) = center;
+        let path = self.points.iter().map(|&(x, y)| (cx + x, cy + y)).collect::<Vec<_>>();
+        write_thick_path(&path, false, self.style.stroke_width, self.style.line_join, buf);
+    }
+
+    /// Una polilínea es una figura abierta, por lo que no tiene relleno.
+    fn write_fill_points_at(&self, _buf: &mut Vec<PixelCoord>, _center: PixelCoord) {}
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
@@ -1,15 +1,31 @@
 //! Figuras geométricas 2D
+mod arc;
+mod bezier;
 mod circle;
+mod line;
+mod polygon;
+mod region;
 mod shape;
 mod square;
-pub use circle::{Circle, Ellipse};
+mod transform;
+pub use arc::{Arc, ArcKind};
+pub use bezier::Bezier;
+pub use circle::{Circle, Ellipse, RotatedEllipse};
+pub use line::{Line, Polyline};
+pub use polygon::Polygon;
+pub use region::Region;
+use serde::{Deserialize, Serialize};
+use shape::DrawableShape;
 pub use shape::ShapeObject;
-pub use square::Square;
+pub use square::{RoundedRect, Square};
+pub use transform::Transform;
 
+use crate::algorithms::{ClipRect, CoveragePoint, LineJoin, thick_path_extent};
 use crate::{Color, PixelCoord, Vertex};
 
 /// Estilo de una figura geométrica.
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ShapeStyle {
     /// Color del borde de la figura
     pub stroke_color: Option<Color>,
@@ -17,10 +33,23 @@ pub struct ShapeStyle {
     pub fill_color: Option<Color>,
     /// Describe el grosor del bordea
     pub stroke_width: f32,
+    /// Dibuja el borde con antialiasing (ver [`Shape::write_aa_outline_points_at`]). Solo aplica a
+    /// bordes de un pixel de grosor
+    pub anti_aliasing: bool,
+    /// Forma de unir los lados del borde cuando es más grueso que un pixel
+    pub line_join: LineJoin,
 }
 
 impl ShapeStyle {
-    fn new() -> Self { Self { stroke_color: Some(Color::BLACK), fill_color: None, stroke_width: 1.0 } }
+    fn new() -> Self {
+        Self {
+            stroke_color: Some(Color::BLACK),
+            fill_color: None,
+            stroke_width: 1.0,
+            anti_aliasing: false,
+            line_join: LineJoin::Miter,
+        }
+    }
 
     /// Cambia el grosor del borde del estilo.
     pub fn stroke_width(self, width: impl Into<f32>) -> Self { Self { stroke_width: width.into(), ..self } }
@@ -31,12 +60,18 @@ impl ShapeStyle {
     /// Cambia el color de relleno del estilo.
     pub fn fill_color(self, color: impl Into<Color>) -> Self { Self { fill_color: Some(color.into()), ..self } }
 
+    /// Activa o desactiva el antialiasing del borde.
+    pub fn anti_aliasing(self, enabled: bool) -> Self { Self { anti_aliasing: enabled, ..self } }
+
+    /// Cambia la forma de unir los lados del borde.
+    pub fn line_join(self, line_join: LineJoin) -> Self { Self { line_join, ..self } }
+
     /// Revisa si el estilo puede implicar una figura transparente.
     pub fn is_transparent(&self) -> bool {
         #[allow(clippy::match_like_matches_macro)]
         match self {
             Self { stroke_color: None, fill_color: None, .. } => true,
-            Self { stroke_color: Some(_), fill_color: None, stroke_width: 0.0 } => true,
+            Self { stroke_color: Some(_), fill_color: None, stroke_width: 0.0, .. } => true,
             _ => false,
         }
     }
@@ -69,6 +104,179 @@ pub trait Shape {
         self.write_outline_points_at(buf, center);
     }
 
+    /// Escribe al buffer dado los puntos del contorno del objeto que están dentro del rectángulo de
+    /// recorte.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto genera el contorno completo con [`Shape::write_outline_points_at`] y descarta
+    /// los puntos fuera del rectángulo. Las figuras grandes deberían evitar generar los puntos
+    /// que no son visibles.
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        let mut outline = self.to_outline_points(center);
+        outline.retain(|&point| clip.contains(point));
+        buf.append(&mut outline);
+    }
+
+    /// Escribe al buffer dado los puntos visibles del contorno del objeto, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::write_clipped_outline_points_at`] con el centro en (0, 0).
+    fn write_clipped_outline_points(&self, buf: &mut Vec<PixelCoord>, clip: &ClipRect) {
+        let center = (0, 0);
+        self.write_clipped_outline_points_at(buf, center, clip);
+    }
+
+    /// Escribe al buffer dado los puntos que forman el contorno del objeto junto con su cobertura,
+    /// para dibujarlo con antialiasing.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::write_outline_points_at`] con cobertura completa, lo cual solo
+    /// es correcto para figuras con lados horizontales y verticales.
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        let outline = self.to_outline_points(center);
+        buf.extend(outline.into_iter().map(|point| (point, 1.0)));
+    }
+
+    /// Escribe al buffer dado los puntos del contorno con su cobertura, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::write_aa_outline_points_at`] con el centro en (0, 0).
+    fn write_aa_outline_points(&self, buf: &mut Vec<CoveragePoint>) {
+        let center = (0, 0);
+        self.write_aa_outline_points_at(buf, center);
+    }
+
+    /// Escribe al buffer dado los pixeles que cubre el borde del objeto, con el grosor
+    /// [`ShapeStyle::stroke_width`] y las uniones [`ShapeStyle::line_join`] de su estilo.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto dibuja un disco del grosor del borde en cada punto del contorno, lo cual
+    /// equivale a usar [`LineJoin::Round`].
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let width = self.style().stroke_width;
+        let mut stroke = Vec::new();
+        for point in self.to_outline_points(center) {
+            crate::algorithms::write_thick_path(&[point], false, width, LineJoin::Round, &mut stroke);
+        }
+        // Los discos de puntos vecinos se superponen
+        stroke.sort_unstable();
+        stroke.dedup();
+        buf.append(&mut stroke);
+    }
+
+    /// Escribe al buffer dado los pixeles que cubre el borde del objeto, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::write_stroke_points_at`] con el centro en (0, 0).
+    fn write_stroke_points(&self, buf: &mut Vec<PixelCoord>) {
+        let center = (0, 0);
+        self.write_stroke_points_at(buf, center);
+    }
+
+    /// Escribe al buffer dado los puntos que forman el relleno del objeto.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto rellena cada fila entre los extremos del contorno (ver
+    /// [`crate::algorithms::flood_fill`]), lo cual solo es correcto para figuras convexas.
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let outline = self.to_outline_points(center);
+        crate::algorithms::flood_fill(&outline, buf);
+    }
+
+    /// Escribe al buffer dado los puntos que forman el relleno del objeto, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::write_fill_points_at`] con el centro en (0, 0).
+    fn write_fill_points(&self, buf: &mut Vec<PixelCoord>) {
+        let center = (0, 0);
+        self.write_fill_points_at(buf, center);
+    }
+
+    /// Revisa si el punto dado está a lo más a `tolerance` pixeles del borde del objeto, contando
+    /// el grosor [`ShapeStyle::stroke_width`] de su estilo.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto compara el punto con cada punto de [`Shape::write_outline_points_at`]. Las
+    /// figuras con una descripción analítica deberían medir la distancia sin generar su contorno.
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        let reach = outline_reach(self.style(), tolerance);
+        let (px, py) = point;
+        self.to_outline_points(center).into_iter().any(|(x, y)| ((x - px) as f32).hypot((y - py) as f32) <= reach)
+    }
+
+    /// Revisa si el punto dado está cerca del borde del objeto, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::hit_outline_at`] con el centro en (0, 0).
+    fn hit_outline(&self, point: PixelCoord, tolerance: f32) -> bool {
+        let center = (0, 0);
+        self.hit_outline_at(point, center, tolerance)
+    }
+
+    /// Revisa si el punto dado es parte del relleno del objeto. Las figuras abiertas, como las
+    /// líneas, no contienen ningún punto.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto busca el punto entre los de [`Shape::write_fill_points_at`], lo cual toma un
+    /// tiempo proporcional al área de la figura. Las figuras con una descripción analítica deberían
+    /// revisarlo directamente.
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        let mut fill = Vec::new();
+        self.write_fill_points_at(&mut fill, center);
+        fill.contains(&point)
+    }
+
+    /// Revisa si el punto dado es parte del relleno del objeto, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::contains_point_at`] con el centro en (0, 0).
+    fn contains_point(&self, point: PixelCoord) -> bool {
+        let center = (0, 0);
+        self.contains_point_at(point, center)
+    }
+
+    /// Caja delimitadora de los pixeles que cubre el objeto, con el grosor de su borde, o `None` si
+    /// no cubre ningún pixel.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto se calcula a partir del borde, ya que el relleno queda dentro de él. Las figuras
+    /// sin borde usan su relleno. Las figuras con una descripción analítica deberían calcular la
+    /// caja directamente.
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let mut points = Vec::new();
+        match self.style().stroke_width > 1.0 {
+            true => self.write_stroke_points_at(&mut points, center),
+            false => self.write_outline_points_at(&mut points, center),
+        }
+        if points.is_empty() {
+            self.write_fill_points_at(&mut points, center);
+        }
+        points_bounds(&points)
+    }
+
+    /// Caja delimitadora del objeto, centrado en el origen.
+    ///
+    /// # Nota
+    ///
+    /// Por defecto utiliza [`Shape::bounding_box_at`] con el centro en (0, 0).
+    fn bounding_box(&self) -> Option<ClipRect> {
+        let center = (0, 0);
+        self.bounding_box_at(center)
+    }
+
     /// Devuelve el estilo de la figura.
     fn style(&self) -> &ShapeStyle;
 
@@ -93,3 +301,151 @@ pub trait Shape {
         writes
     }
 }
+
+/// Distancia máxima entre un punto y el contorno de una figura con el estilo dado para que el punto
+/// toque su borde, contando el grosor del borde (ver [`Shape::hit_outline_at`]).
+fn outline_reach(style: &ShapeStyle, tolerance: f32) -> f32 { tolerance + (style.stroke_width - 1.0).max(0.0) / 2.0 }
+
+/// Revisa si un punto a la distancia `distance` del contorno ideal de una figura con el estilo dado
+/// toca su borde. Los pixeles del contorno se alejan hasta medio pixel del contorno ideal.
+fn hits_outline(distance: f32, style: &ShapeStyle, tolerance: f32) -> bool {
+    distance.abs() <= outline_reach(style, tolerance) + 0.5
+}
+
+/// Caja delimitadora de los puntos dados, o `None` si no hay ninguno.
+fn points_bounds(points: &[PixelCoord]) -> Option<ClipRect> {
+    let (&first, rest) = points.split_first()?;
+    let bounds = rest.iter().fold(ClipRect::new(first, first), |bounds, &(x, y)| {
+        ClipRect::new((bounds.x_min.min(x), bounds.y_min.min(y)), (bounds.x_max.max(x), bounds.y_max.max(y)))
+    });
+    Some(bounds)
+}
+
+/// Distancia del punto dado al segmento entre `p0` y `p1`.
+fn segment_distance((x, y): PixelCoord, (x0, y0): PixelCoord, (x1, y1): PixelCoord) -> f32 {
+    let (dx, dy) = ((x1 - x0) as f32, (y1 - y0) as f32);
+    let (px, py) = ((x - x0) as f32, (y - y0) as f32);
+    let t = match dx * dx + dy * dy {
+        0.0 => 0.0,
+        length2 => ((px * dx + py * dy) / length2).clamp(0.0, 1.0),
+    };
+    (px - t * dx).hypot(py - t * dy)
+}
+
+/// Caja delimitadora de una figura con el estilo dado cuyo contorno va de `min` a `max`, contando
+/// el grosor del borde igual que [`Shape::bounding_box_at`].
+fn outline_bounds(min: PixelCoord, max: PixelCoord, style: &ShapeStyle) -> ClipRect {
+    if style.stroke_width <= 1.0 {
+        return ClipRect::new(min, max);
+    }
+    let (x_min, x_max) = thick_path_extent(min.0, max.0, style.stroke_width);
+    let (y_min, y_max) = thick_path_extent(min.1, max.1, style.stroke_width);
+    ClipRect::new((x_min, y_min), (x_max, y_max))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{outline_reach, points_bounds};
+    use crate::algorithms::{ClipRect, FillRule, LineAlgorithm};
+    use crate::geometries::Transform;
+    use crate::{Shape, ShapeObject};
+
+    #[test]
+    fn clipped_outlines_match_filtered() {
+        let clip = ClipRect::from_dimensions((40, 30));
+        let shapes = [
+            ShapeObject::new_square(30, (5, 5)),
+            ShapeObject::new_rectangle(80, 10, (20, 28)),
+            ShapeObject::new_circle(25, (35, 0)),
+            ShapeObject::new_ellipse(50, 12, (20, 15)),
+            ShapeObject::new_line((-30, 40), (70, -10), LineAlgorithm::Dda),
+            ShapeObject::new_polyline(&[(-10, 5), (20, 20), (20, 20), (60, 0), (45, 50)], LineAlgorithm::Bresenham),
+            ShapeObject::new_polygon(&[(-5, -5), (50, 10), (10, 45)], FillRule::EvenOdd),
+            // Segmentos de longitud cero dentro del rectángulo y en el margen de un pixel
+            ShapeObject::new_line((10, 10), (10, 10), LineAlgorithm::Bresenham),
+            ShapeObject::new_line((-1, 5), (-1, 5), LineAlgorithm::Bresenham),
+            ShapeObject::new_line((-1, 5), (-1, 5), LineAlgorithm::Dda),
+            ShapeObject::new_polyline(&[(-1, 5), (-1, 5), (20, 30), (20, 30), (40, 12)], LineAlgorithm::Dda),
+            ShapeObject::new_polygon(&[(-1, -1), (-1, -1), (50, 10), (40, 30), (40, 30)], FillRule::EvenOdd),
+            // El último vértice repite el primero, y el lado que cierra el polígono tiene longitud cero
+            ShapeObject::new_polygon(&[(40, 20), (-20, 10), (15, 30), (40, 20)], FillRule::NonZero),
+        ];
+        for shape in shapes {
+            let mut expected = Vec::new();
+            shape.write_outline_points(&mut expected);
+            expected.retain(|&point| clip.contains(point));
+            expected.sort();
+
+            let mut clipped = Vec::new();
+            shape.write_clipped_outline_points(&mut clipped, &clip);
+            clipped.sort();
+            assert_eq!(clipped, expected, "{}", shape.shape_name());
+        }
+    }
+
+    #[test]
+    fn analytic_queries_match_rasterized() {
+        let styled = |mut shape: ShapeObject, width: f32| {
+            *shape.style_mut() = shape.style_mut().stroke_color(crate::Color::BLACK).stroke_width(width);
+            shape
+        };
+        let shapes = [
+            styled(ShapeObject::new_square(30, (5, 5)), 1.0),
+            styled(ShapeObject::new_rectangle(21, 10, (20, 28)), 3.0),
+            styled(ShapeObject::new_rectangle(21, 10, (20, 28)), 4.0),
+            styled(ShapeObject::new_rounded_rect(40, 24, 7, (0, 0)), 1.0),
+            styled(ShapeObject::new_rounded_rect(40, 24, 7, (0, 0)), 5.0),
+            styled(ShapeObject::new_circle(25, (35, 0)), 1.0),
+            styled(ShapeObject::new_circle(7, (3, 2)), 1.0),
+            styled(ShapeObject::new_circle(25, (35, 0)), 4.0),
+            styled(ShapeObject::new_ellipse(50, 12, (20, 15)), 1.0),
+            styled(ShapeObject::new_ellipse(13, 30, (20, 15)), 3.0),
+            styled(ShapeObject::new_ellipse(30, 12, (0, 0)).transform(Transform::rotate(0.5)), 1.0),
+            styled(ShapeObject::new_ellipse(30, 12, (0, 0)).transform(Transform::rotate(0.5)), 6.0),
+            styled(ShapeObject::new_rectangle(30, 12, (0, 0)).transform(Transform::rotate(0.3)), 1.0),
+            styled(ShapeObject::new_star(5, 30, 12, (0, 0)), 1.0),
+            styled(ShapeObject::new_polygon(&[(-5, -5), (50, 10), (10, 45)], FillRule::NonZero), 3.0),
+            ShapeObject::new_region(&[(0, 0), (1, 0), (5, 3), (-2, 7)], crate::Color::BLACK).transform(Transform::rotate(0.7)),
+        ];
+        for shape in shapes {
+            let name = format!("{} de ancho {}", shape.shape_name(), shape.style().stroke_width);
+            let (mut outline, mut fill, mut painted) = (Vec::new(), Vec::new(), Vec::new());
+            shape.write_outline_points(&mut outline);
+            shape.write_fill_points(&mut fill);
+            match shape.style().stroke_width > 1.0 {
+                true => shape.write_stroke_points(&mut painted),
+                false => painted.clone_from(&outline),
+            }
+            if painted.is_empty() {
+                painted.clone_from(&fill);
+            }
+
+            // La caja se calcula en forma cerrada, y puede diferir en un pixel de la rasterizada
+            let expected = points_bounds(&painted).unwrap();
+            let bounds = shape.bounding_box().unwrap();
+            let sides = [
+                (bounds.x_min, expected.x_min),
+                (bounds.y_min, expected.y_min),
+                (bounds.x_max, expected.x_max),
+                (bounds.y_max, expected.y_max),
+            ];
+            assert!(sides.iter().all(|(a, b)| (a - b).abs() <= 1), "{name}: {bounds:?} en vez de {expected:?}");
+
+            // Las pruebas analíticas sólo difieren de las rasterizadas junto al contorno
+            let reach = outline_reach(shape.style(), 2.0);
+            for y in expected.y_min - 3..=expected.y_max + 3 {
+                for x in expected.x_min - 3..=expected.x_max + 3 {
+                    let nearest =
+                        outline.iter().map(|&(ox, oy)| ((ox - x) as f32).hypot((oy - y) as f32)).fold(f32::INFINITY, f32::min);
+                    if shape.contains_point((x, y)) != fill.contains(&(x, y)) {
+                        assert!(nearest < 1.5, "{name}: el relleno difiere lejos del contorno en {:?}", (x, y));
+                    }
+                    match shape.hit_outline((x, y), 2.0) {
+                        true => assert!(nearest <= reach + 1.0, "{name}: {:?} está lejos del contorno", (x, y)),
+                        false => assert!(nearest > reach, "{name}: {:?} está sobre el contorno", (x, y)),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::{Shape, ShapeStyle, Transform};
+use crate::PixelCoord;
+use crate::algorithms::{
+    ClipRect, CoveragePoint, FillRule, LineAlgorithm, polygon_contains, write_polygon_scanline_fill, write_thick_path,
+    write_wu_line,
+};
+
+/// Polígono cerrado definido por sus vértices relativos al centro del objeto.
+#[derive(Clone)]
+pub struct Polygon {
+    vertices: Vec<PixelCoord>,
+    fill_rule: FillRule,
+    algorithm: LineAlgorithm,
+    pub style: ShapeStyle,
+}
+
+impl Polygon {
+    /// Crea un nuevo polígono con los vértices dados, relativos al centro del objeto.
+    ///
+    /// El polígono se cierra uniendo el último vértice con el primero.
+    pub fn new(vertices: Vec<PixelCoord>) -> Self {
+        Self { vertices, fill_rule: FillRule::default(), algorithm: LineAlgorithm::default(), style: ShapeStyle::new() }
+    }
+
+    /// Modifica la regla utilizada para rellenar el polígono.
+    pub fn fill_rule(self, fill_rule: FillRule) -> Self { Self { fill_rule, ..self } }
+
+    /// Modifica el algoritmo utilizado para trazar los lados.
+    pub fn algorithm(self, algorithm: LineAlgorithm) -> Self { Self { algorithm, ..self } }
+
+    /// Devuelve los vértices del polígono, relativos al centro del objeto.
+    pub fn vertices(&self) -> &[PixelCoord] { &self.vertices }
+
+    /// Modifica el estilo del polígono.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a los vértices del polígono.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let vertices = self.vertices.iter().map(|&vertex| transform.apply_linear_to_pixel(vertex)).collect();
+        Self { vertices, ..self.clone() }
+    }
+
+    /// Devuelve los vértices trasladados al centro dado.
+    fn vertices_at(&self, center: PixelCoord) -> impl ExactSizeIterator<Item = PixelCoord> + Clone + '_ {
+        let (cx, cy) = center;
+        self.vertices.iter().map(move |&(x, y)| (cx + x, cy + y))
+    }
+}
+
+impl Shape for Polygon {
+    /// Escribe al buffer dado los puntos de cada lado del polígono, incluyendo el lado que une el
+    /// último vértice con el primero.
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        use itertools::Itertools;
+
+        match self.vertices.len() {
+            0 => {}
+            1 => buf.extend(self.vertices_at(center)),
+            _ => {
+                for (p0, p1) in self.vertices_at(center).circular_tuple_windows() {
+                    let start = buf.len();
+                    self.algorithm.write_line(p0, p1, buf);
+                    // El último punto del lado es el primero del siguiente lado
+                    if buf.len() > start + 1 {
+                        buf.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        use itertools::Itertools;
+
+        match self.vertices.len() {
+            0 => {}
+            1 => buf.extend(self.vertices_at(center).filter(|&point| clip.contains(point))),
+            _ => {
+                for (p0, p1) in self.vertices_at(center).circular_tuple_windows() {
+                    self.algorithm.write_line_clipped(p0, p1, clip, buf);
+                    // El último punto del lado es el primero del siguiente lado
+                    if p0 != p1 && buf.last() == Some(&p1) {
+                        buf.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Escribe al buffer dado los puntos de cada lado del polígono con su cobertura.
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        use itertools::Itertools;
+
+        match self.vertices.len() {
+            0 => {}
+            1 => buf.extend(self.vertices_at(center).map(|point| (point, 1.0))),
+            _ => self.vertices_at(center).circular_tuple_windows().for_each(|(p0, p1)| write_wu_line(p0, p1, buf)),
+        }
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let path = self.vertices_at(center).collect::<Vec<_>>();
+        write_thick_path(&path, true, self.style.stroke_width, self.style.line_join, buf);
+    }
+
+    /// Escribe al buffer dado los puntos interiores del polígono usando un relleno por líneas de
+    /// barrido, según la [`FillRule`] del polígono.
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let vertices = self.vertices_at(center).collect::<Vec<_>>();
+        write_polygon_scanline_fill(&vertices, self.fill_rule, buf);
+    }
+
+    /// Revisa si el punto dado está cerca de alguno de los lados, midiendo la distancia a cada
+    /// segmento.
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        use itertools::Itertools;
+
+        let distance = match self.vertices.as_slice() {
+            [] => return false,
+            &[(x, y)] => super::segment_distance(point, (center.0 + x, center.1 + y), (center.0 + x, center.1 + y)),
+            _ => {
+                self.vertices_at(center)
+                    .circular_tuple_windows()
+                    .map(|(p0, p1)| super::segment_distance(point, p0, p1))
+                    .fold(f32::INFINITY, f32::min)
+            }
+        };
+        super::hits_outline(distance, &self.style, tolerance)
+    }
+
+    /// Revisa si el punto dado es parte del relleno, según la [`FillRule`] del polígono, sin
+    /// generar los demás pixeles del relleno.
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        let vertices = self.vertices_at(center).collect::<Vec<_>>();
+        polygon_contains(&vertices, self.fill_rule, point)
+    }
+
+    /// Caja delimitadora de los vértices. Con un borde grueso se usan los pixeles del borde, ya que
+    /// las uniones en punta sobresalen de los vértices.
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let mut points = Vec::new();
+        match self.style.stroke_width > 1.0 {
+            true => self.write_stroke_points_at(&mut points, center),
+            false => points.extend(self.vertices_at(center)),
+        }
+        super::points_bounds(&points)
+    }
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use super::{Shape, ShapeStyle, Transform};
+use crate::algorithms::ClipRect;
+use crate::{Color, PixelCoord};
+
+/// Conjunto arbitrario de pixeles rellenados, como los generados por la herramienta de cubeta.
+///
+/// Los pixeles son relativos al centro del objeto. Una región no tiene contorno.
+#[derive(Clone)]
+pub struct Region {
+    points: Vec<PixelCoord>,
+    /// Los mismos pixeles que `points`, para revisar si la región contiene un pixel sin
+    /// recorrerlos.
+    lookup: HashSet<PixelCoord>,
+    pub style: ShapeStyle,
+}
+
+impl Region {
+    /// Crea una nueva región con los pixeles dados, relativos al centro del objeto, rellenada con
+    /// el color dado.
+    pub fn new(points: Vec<PixelCoord>, color: Color) -> Self {
+        let style = ShapeStyle { stroke_color: None, fill_color: Some(color), ..ShapeStyle::new() };
+        Self::with_points(points, style)
+    }
+
+    /// Crea una región con los pixeles y el estilo dados.
+    fn with_points(points: Vec<PixelCoord>, style: ShapeStyle) -> Self {
+        Self { lookup: points.iter().copied().collect(), points, style }
+    }
+
+    /// Devuelve los pixeles de la región, relativos al centro del objeto.
+    pub fn points(&self) -> &[PixelCoord] { &self.points }
+
+    /// Modifica el estilo de la región.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a la región.
+    ///
+    /// Cada pixel de la caja delimitadora de la región transformada se lleva de vuelta con la
+    /// transformación inversa, y se conserva si cae en un pixel de la región original. Así la
+    /// región no queda con huecos al rotarla o agrandarla.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        use itertools::Itertools;
+
+        let Some(inverse) = transform.inverse() else {
+            let points = self.points.iter().map(|&point| transform.apply_linear_to_pixel(point)).unique().collect();
+            return Self::with_points(points, self.style);
+        };
+        let corners = self.points.iter().map(|&point| transform.apply_linear_to_pixel(point)).collect::<Vec<_>>();
+        let (Some((x0, x1)), Some((y0, y1))) =
+            (corners.iter().map(|p| p.0).minmax().into_option(), corners.iter().map(|p| p.1).minmax().into_option())
+        else {
+            return self.clone();
+        };
+
+        let points = (y0 - 1..=y1 + 1)
+            .cartesian_product(x0 - 1..=x1 + 1)
+            .map(|(y, x)| (x, y))
+            .filter(|&point| self.lookup.contains(&inverse.apply_linear_to_pixel(point)))
+            .collect();
+        Self::with_points(points, self.style)
+    }
+}
+
+impl Shape for Region {
+    /// Una región no tiene contorno.
+    fn write_outline_points_at(&self, _buf: &mut Vec<PixelCoord>, _center: PixelCoord) {}
+
+    /// Escribe al buffer dado los pixeles de la región trasladados al centro dado.
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (cx, cy) = center;
+        buf.extend(self.points.iter().map(|&(x, y)| (cx + x, cy + y)));
+    }
+
+    fn contains_point_at(&self, (x, y): PixelCoord, (cx, cy): PixelCoord) -> bool { self.lookup.contains(&(x - cx, y - cy)) }
+
+    fn bounding_box_at(&self, (cx, cy): PixelCoord) -> Option<ClipRect> {
+        let bounds = super::points_bounds(&self.points)?;
+        Some(ClipRect::new((cx + bounds.x_min, cy + bounds.y_min), (cx + bounds.x_max, cy + bounds.y_max)))
+    }
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
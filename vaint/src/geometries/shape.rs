@@ -1,10 +1,23 @@
-use crate::geometries::{Circle, Ellipse, Square};
-use crate::{PixelCoord, Shape};
+use std::f32::consts::{PI, TAU};
+
+use crate::algorithms::{ClipRect, ClipRegion, CoveragePoint, FillRule, LineAlgorithm};
+use crate::geometries::{
+    Arc, ArcKind, Bezier, Circle, Ellipse, Line, Polygon, Polyline, Region, RotatedEllipse, RoundedRect, Square, Transform,
+};
+use crate::{Color, PixelCoord, Shape};
 
 /// Estructura que describe un Objeto 2D dibujable. Incluye información sobre us posición.
 pub struct ShapeObject {
     shape: DrawableShape,
     pub center: PixelCoord,
+    /// Región de la escena fuera de la cual no se dibuja la figura, ni su contorno ni su relleno.
+    pub clip_region: Option<ClipRegion>,
+    /// Transformación de la figura respecto a su centro. La traslación se redondea al pixel más
+    /// cercano y desplaza el centro.
+    pub transform: Transform,
+    /// Identificador de la figura en el panel de control, si la figura vino de él (ver
+    /// [`crate::ipc`]).
+    pub id: Option<u64>,
 }
 
 impl ShapeObject {
@@ -16,7 +29,7 @@ impl ShapeObject {
     fn new<T: Into<DrawableShape>>(shape: T, center: PixelCoord) -> Self {
         debug_assert!(center.0 < i32::MAX, "El centro del objeto está fuera del rango de i32");
         debug_assert!(center.1 < i32::MAX, "El centro del objeto está fuera del rango de i32");
-        Self { shape: shape.into(), center }
+        Self { shape: shape.into(), center, clip_region: None, transform: Transform::IDENTITY, id: None }
     }
 
     /// Construye un objeto cuadrado
@@ -45,13 +58,136 @@ impl ShapeObject {
         Self::new(rectangle, center)
     }
 
+    /// Construye un objeto rectangular con las esquinas redondeadas con el radio dado.
+    pub fn new_rounded_rect(width: u32, height: u32, radius: u32, center: PixelCoord) -> Self {
+        let rounded_rect = RoundedRect::new(width, height, radius);
+        Self::new(rounded_rect, center)
+    }
+
+    /// Construye un objeto de arco de elipse abierto, que va del ángulo `start` al ángulo `end` (en
+    /// radianes, en sentido antihorario).
+    pub fn new_arc(radius_x: u32, radius_y: u32, start: f32, end: f32, center: PixelCoord) -> Self {
+        Self::new(Arc::new(radius_x, radius_y, start, end), center)
+    }
+
+    /// Construye un objeto de sector de elipse (rebanada de pastel), que va del ángulo `start` al
+    /// ángulo `end` (en radianes, en sentido antihorario).
+    pub fn new_pie(radius_x: u32, radius_y: u32, start: f32, end: f32, center: PixelCoord) -> Self {
+        Self::new(Arc::new(radius_x, radius_y, start, end).kind(ArcKind::Pie), center)
+    }
+
+    /// Construye un objeto de segmento de elipse, cerrado por la cuerda entre los ángulos `start` y
+    /// `end` (en radianes, en sentido antihorario).
+    pub fn new_chord(radius_x: u32, radius_y: u32, start: f32, end: f32, center: PixelCoord) -> Self {
+        Self::new(Arc::new(radius_x, radius_y, start, end).kind(ArcKind::Chord), center)
+    }
+
+    /// Construye un objeto lineal entre los puntos dados, trazado con el algoritmo indicado.
+    ///
+    /// El centro del objeto es el punto medio de la línea.
+    pub fn new_line(start: PixelCoord, end: PixelCoord, algorithm: LineAlgorithm) -> Self {
+        let center = ((start.0 + end.0) / 2, (start.1 + end.1) / 2);
+        let relative = |(x, y): PixelCoord| (x - center.0, y - center.1);
+        let line = Line::new(relative(start), relative(end)).algorithm(algorithm);
+        Self::new(line, center)
+    }
+
+    /// Construye un objeto de polilínea que une los puntos dados, trazado con el algoritmo
+    /// indicado.
+    ///
+    /// El centro del objeto es el centro de la caja delimitadora de los puntos.
+    pub fn new_polyline(points: &[PixelCoord], algorithm: LineAlgorithm) -> Self {
+        let (center, relative) = relative_to_bounding_box_center(points);
+        let polyline = Polyline::new(relative).algorithm(algorithm);
+        Self::new(polyline, center)
+    }
+
+    /// Construye un objeto de curva de Bézier con los puntos de control dados, trazada con el
+    /// algoritmo indicado. Con 3 puntos de control la curva es cuadrática, y con 4 es cúbica.
+    ///
+    /// El centro del objeto es el centro de la caja delimitadora de los puntos de control.
+    pub fn new_bezier(control_points: &[PixelCoord], algorithm: LineAlgorithm) -> Self {
+        let (center, relative) = relative_to_bounding_box_center(control_points);
+        let bezier = Bezier::new(relative).algorithm(algorithm);
+        Self::new(bezier, center)
+    }
+
+    /// Construye un objeto poligonal con los vértices dados, rellenado según la regla indicada.
+    ///
+    /// El centro del objeto es el centro de la caja delimitadora de los vértices.
+    pub fn new_polygon(vertices: &[PixelCoord], fill_rule: FillRule) -> Self {
+        let (center, relative) = relative_to_bounding_box_center(vertices);
+        let polygon = Polygon::new(relative).fill_rule(fill_rule);
+        Self::new(polygon, center)
+    }
+
+    /// Construye un objeto con forma de polígono regular de `sides` lados, inscrito en una
+    /// circunferencia del radio dado.
+    ///
+    /// Sin rotación, el primer vértice está justo arriba del centro; `rotation` (en radianes) gira
+    /// el polígono en sentido antihorario.
+    pub fn new_regular_polygon(sides: u32, radius: u32, center: PixelCoord, rotation: f32) -> Self {
+        let vertices = (0..sides).map(|k| polar_vertex(radius as f32, rotation + k as f32 * TAU / sides as f32)).collect();
+        Self::new(Polygon::new(vertices), center)
+    }
+
+    /// Construye un objeto con forma de estrella de `points` puntas, cuyos vértices alternan entre
+    /// las circunferencias de radio `outer_radius` (puntas) e `inner_radius` (hendiduras).
+    ///
+    /// La primera punta está justo arriba del centro.
+    pub fn new_star(points: u32, outer_radius: u32, inner_radius: u32, center: PixelCoord) -> Self {
+        let vertices = (0..2 * points)
+            .map(|k| {
+                let radius = if k % 2 == 0 { outer_radius } else { inner_radius };
+                polar_vertex(radius as f32, k as f32 * PI / points as f32)
+            })
+            .collect();
+        Self::new(Polygon::new(vertices), center)
+    }
+
+    /// Construye un objeto formado por los pixeles dados, rellenados con el color indicado.
+    ///
+    /// El centro del objeto es el centro de la caja delimitadora de los pixeles.
+    pub fn new_region(points: &[PixelCoord], color: Color) -> Self {
+        let (center, relative) = relative_to_bounding_box_center(points);
+        Self::new(Region::new(relative, color), center)
+    }
+
+    /// Limita el dibujo de la figura a la región convexa dada, en coordenadas de la escena.
+    pub fn clip_region(self, clip_region: ClipRegion) -> Self { Self { clip_region: Some(clip_region), ..self } }
+
+    /// Asigna el identificador dado a la figura.
+    pub fn id(self, id: u64) -> Self { Self { id: Some(id), ..self } }
+
+    /// Aplica la transformación dada a la figura, después de su transformación actual.
+    pub fn transform(self, transform: Transform) -> Self { Self { transform: self.transform.then(transform), ..self } }
+
+    /// Llama a la función dada con la figura ya transformada y con el centro dado desplazado por la
+    /// traslación de la transformación.
+    fn with_transformed<R>(&self, center: PixelCoord, f: impl FnOnce(&DrawableShape, PixelCoord) -> R) -> R {
+        let (tx, ty) = self.transform.pixel_translation();
+        let center = (center.0 + tx, center.1 + ty);
+        match self.transform.is_translation() {
+            true => f(&self.shape, center),
+            false => f(&self.shape.transformed(&self.transform), center),
+        }
+    }
+
     /// Obtiene una referencia editable al estilo de la figura.
     pub fn style_mut(&mut self) -> &mut crate::ShapeStyle {
         match &mut self.shape {
             DrawableShape::Square(s) => &mut s.style,
             DrawableShape::Circle(s) => &mut s.style,
             DrawableShape::Ellipse(s) => &mut s.style,
+            DrawableShape::RotatedEllipse(s) => &mut s.style,
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => &mut s.style,
             DrawableShape::Rectangle(s) => &mut s.style,
+            DrawableShape::RoundedRect(s) => &mut s.style,
+            DrawableShape::Line(s) => &mut s.style,
+            DrawableShape::Polyline(s) => &mut s.style,
+            DrawableShape::Bezier(s) => &mut s.style,
+            DrawableShape::Polygon(s) => &mut s.style,
+            DrawableShape::Region(s) => &mut s.style,
         }
     }
 
@@ -60,20 +196,103 @@ impl ShapeObject {
             DrawableShape::Square(_) => "Cuadrado",
             DrawableShape::Circle(_) => "Círculo",
             DrawableShape::Ellipse(_) => "Elipse",
+            DrawableShape::RotatedEllipse(_) => "Elipse rotada",
+            DrawableShape::Arc(_) => "Arco",
+            DrawableShape::Pie(_) => "Sector",
+            DrawableShape::Chord(_) => "Segmento",
             DrawableShape::Rectangle(_) => "Rectángulo",
+            DrawableShape::RoundedRect(_) => "Rectángulo redondeado",
+            DrawableShape::Line(_) => "Línea",
+            DrawableShape::Polyline(_) => "Polilínea",
+            DrawableShape::Bezier(_) => "Curva de Bézier",
+            DrawableShape::Polygon(_) => "Polígono",
+            DrawableShape::Region(_) => "Región",
         }
     }
 }
 
 impl Shape for ShapeObject {
     fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
-        self.shape.write_outline_points_at(buf, center);
+        self.with_transformed(center, |shape, center| shape.write_outline_points_at(buf, center));
     }
 
     /// Escribe al buffer dado los puntos que forman el contorno del objeto, centrado en
     /// [`ShapeObject::center`].
     fn write_outline_points(&self, buf: &mut Vec<PixelCoord>) { self.write_outline_points_at(buf, self.center); }
 
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        self.with_transformed(center, |shape, center| shape.write_clipped_outline_points_at(buf, center, clip));
+    }
+
+    /// Escribe al buffer dado los puntos visibles del contorno del objeto, centrado en
+    /// [`ShapeObject::center`].
+    fn write_clipped_outline_points(&self, buf: &mut Vec<PixelCoord>, clip: &ClipRect) {
+        self.write_clipped_outline_points_at(buf, self.center, clip);
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        self.with_transformed(center, |shape, center| shape.write_aa_outline_points_at(buf, center));
+    }
+
+    /// Escribe al buffer dado los puntos del contorno con su cobertura, centrado en
+    /// [`ShapeObject::center`].
+    fn write_aa_outline_points(&self, buf: &mut Vec<CoveragePoint>) { self.write_aa_outline_points_at(buf, self.center); }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        self.with_transformed(center, |shape, center| shape.write_stroke_points_at(buf, center));
+    }
+
+    /// Escribe al buffer dado los pixeles que cubre el borde del objeto, centrado en
+    /// [`ShapeObject::center`].
+    fn write_stroke_points(&self, buf: &mut Vec<PixelCoord>) { self.write_stroke_points_at(buf, self.center); }
+
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        self.with_transformed(center, |shape, center| shape.write_fill_points_at(buf, center));
+    }
+
+    /// Escribe al buffer dado los puntos que forman el relleno del objeto, centrado en
+    /// [`ShapeObject::center`].
+    fn write_fill_points(&self, buf: &mut Vec<PixelCoord>) { self.write_fill_points_at(buf, self.center); }
+
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        self.with_transformed(center, |shape, center| shape.hit_outline_at(point, center, tolerance))
+    }
+
+    /// Revisa si el punto dado está cerca del borde del objeto, centrado en
+    /// [`ShapeObject::center`]. Los puntos fuera de [`ShapeObject::clip_region`] no se
+    /// consideran.
+    fn hit_outline(&self, point: PixelCoord, tolerance: f32) -> bool {
+        self.clip_region.as_ref().is_none_or(|region| region.contains(point))
+            && self.hit_outline_at(point, self.center, tolerance)
+    }
+
+    /// Revisa si el punto dado es parte del relleno del objeto.
+    ///
+    /// Las regiones transformadas no se transforman: el punto se lleva de vuelta con la
+    /// transformación inversa, igual que en [`Region::transformed`], y se busca en la región
+    /// original.
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        if let (DrawableShape::Region(region), Some(inverse)) = (&self.shape, self.transform.inverse()) {
+            let (tx, ty) = self.transform.pixel_translation();
+            let relative = (point.0 - center.0 - tx, point.1 - center.1 - ty);
+            return region.contains_point_at(inverse.apply_linear_to_pixel(relative), (0, 0));
+        }
+        self.with_transformed(center, |shape, center| shape.contains_point_at(point, center))
+    }
+
+    /// Revisa si el punto dado es parte del relleno del objeto, centrado en
+    /// [`ShapeObject::center`]. Los puntos fuera de [`ShapeObject::clip_region`] no se consideran.
+    fn contains_point(&self, point: PixelCoord) -> bool {
+        self.clip_region.as_ref().is_none_or(|region| region.contains(point)) && self.contains_point_at(point, self.center)
+    }
+
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        self.with_transformed(center, |shape, center| shape.bounding_box_at(center))
+    }
+
+    /// Caja delimitadora del objeto, centrado en [`ShapeObject::center`].
+    fn bounding_box(&self) -> Option<ClipRect> { self.bounding_box_at(self.center) }
+
     fn style(&self) -> &crate::ShapeStyle { self.shape.style() }
 }
 
@@ -82,19 +301,179 @@ impl Shape for ShapeObject {
 /// # Elemeto Privado
 ///
 /// Revisar [`ShapeObject`] para más detalles.
-enum DrawableShape {
+pub(super) enum DrawableShape {
     Square(Square),
     Rectangle(Square),
+    RoundedRect(RoundedRect),
     Circle(Circle),
     Ellipse(Ellipse),
+    RotatedEllipse(RotatedEllipse),
+    Arc(Arc),
+    Pie(Arc),
+    Chord(Arc),
+    Line(Line),
+    Polyline(Polyline),
+    Bezier(Bezier),
+    Polygon(Polygon),
+    Region(Region),
 }
+impl DrawableShape {
+    /// Aplica la parte lineal de la transformación dada a la figura. Las figuras que no conservan
+    /// su forma se convierten en otra variante, como los rectángulos en polígonos o las elipses en
+    /// elipses rotadas.
+    fn transformed(&self, transform: &Transform) -> DrawableShape {
+        match self {
+            DrawableShape::Square(s) | DrawableShape::Rectangle(s) => s.transformed(transform).into(),
+            DrawableShape::RoundedRect(s) => s.transformed(transform).into(),
+            DrawableShape::Circle(s) => s.transformed(transform).into(),
+            DrawableShape::Ellipse(s) => s.transformed(transform).into(),
+            DrawableShape::RotatedEllipse(s) => s.transformed(transform).into(),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.transformed(transform),
+            DrawableShape::Line(s) => s.transformed(transform).into(),
+            DrawableShape::Polyline(s) => s.transformed(transform).into(),
+            DrawableShape::Bezier(s) => s.transformed(transform).into(),
+            DrawableShape::Polygon(s) => s.transformed(transform).into(),
+            DrawableShape::Region(s) => s.transformed(transform).into(),
+        }
+    }
+}
+
 impl Shape for DrawableShape {
     fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
         match self {
             DrawableShape::Square(s) => s.write_outline_points_at(buf, center),
             DrawableShape::Circle(s) => s.write_outline_points_at(buf, center),
             DrawableShape::Ellipse(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::RotatedEllipse(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.write_outline_points_at(buf, center),
             DrawableShape::Rectangle(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::RoundedRect(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::Line(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::Polyline(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::Bezier(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::Polygon(s) => s.write_outline_points_at(buf, center),
+            DrawableShape::Region(s) => s.write_outline_points_at(buf, center),
+        }
+    }
+
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        match self {
+            DrawableShape::Square(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Circle(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Ellipse(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::RotatedEllipse(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => {
+                s.write_clipped_outline_points_at(buf, center, clip)
+            }
+            DrawableShape::Rectangle(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::RoundedRect(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Line(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Polyline(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Bezier(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Polygon(s) => s.write_clipped_outline_points_at(buf, center, clip),
+            DrawableShape::Region(s) => s.write_clipped_outline_points_at(buf, center, clip),
+        }
+    }
+
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        match self {
+            DrawableShape::Square(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Circle(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Ellipse(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::RotatedEllipse(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Rectangle(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::RoundedRect(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Line(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Polyline(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Bezier(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Polygon(s) => s.write_aa_outline_points_at(buf, center),
+            DrawableShape::Region(s) => s.write_aa_outline_points_at(buf, center),
+        }
+    }
+
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        match self {
+            DrawableShape::Square(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Circle(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Ellipse(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::RotatedEllipse(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Rectangle(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::RoundedRect(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Line(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Polyline(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Bezier(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Polygon(s) => s.write_stroke_points_at(buf, center),
+            DrawableShape::Region(s) => s.write_stroke_points_at(buf, center),
+        }
+    }
+
+    fn write_fill_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        match self {
+            DrawableShape::Square(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Circle(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Ellipse(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::RotatedEllipse(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Rectangle(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::RoundedRect(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Line(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Polyline(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Bezier(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Polygon(s) => s.write_fill_points_at(buf, center),
+            DrawableShape::Region(s) => s.write_fill_points_at(buf, center),
+        }
+    }
+
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        match self {
+            DrawableShape::Square(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Circle(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Ellipse(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::RotatedEllipse(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Rectangle(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::RoundedRect(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Line(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Polyline(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Bezier(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Polygon(s) => s.hit_outline_at(point, center, tolerance),
+            DrawableShape::Region(s) => s.hit_outline_at(point, center, tolerance),
+        }
+    }
+
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        match self {
+            DrawableShape::Square(s) => s.contains_point_at(point, center),
+            DrawableShape::Circle(s) => s.contains_point_at(point, center),
+            DrawableShape::Ellipse(s) => s.contains_point_at(point, center),
+            DrawableShape::RotatedEllipse(s) => s.contains_point_at(point, center),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.contains_point_at(point, center),
+            DrawableShape::Rectangle(s) => s.contains_point_at(point, center),
+            DrawableShape::RoundedRect(s) => s.contains_point_at(point, center),
+            DrawableShape::Line(s) => s.contains_point_at(point, center),
+            DrawableShape::Polyline(s) => s.contains_point_at(point, center),
+            DrawableShape::Bezier(s) => s.contains_point_at(point, center),
+            DrawableShape::Polygon(s) => s.contains_point_at(point, center),
+            DrawableShape::Region(s) => s.contains_point_at(point, center),
+        }
+    }
+
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        match self {
+            DrawableShape::Square(s) => s.bounding_box_at(center),
+            DrawableShape::Circle(s) => s.bounding_box_at(center),
+            DrawableShape::Ellipse(s) => s.bounding_box_at(center),
+            DrawableShape::RotatedEllipse(s) => s.bounding_box_at(center),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.bounding_box_at(center),
+            DrawableShape::Rectangle(s) => s.bounding_box_at(center),
+            DrawableShape::RoundedRect(s) => s.bounding_box_at(center),
+            DrawableShape::Line(s) => s.bounding_box_at(center),
+            DrawableShape::Polyline(s) => s.bounding_box_at(center),
+            DrawableShape::Bezier(s) => s.bounding_box_at(center),
+            DrawableShape::Polygon(s) => s.bounding_box_at(center),
+            DrawableShape::Region(s) => s.bounding_box_at(center),
         }
     }
 
@@ -103,7 +482,15 @@ impl Shape for DrawableShape {
             DrawableShape::Square(s) => s.style(),
             DrawableShape::Circle(s) => s.style(),
             DrawableShape::Ellipse(s) => s.style(),
+            DrawableShape::RotatedEllipse(s) => s.style(),
+            DrawableShape::Arc(s) | DrawableShape::Pie(s) | DrawableShape::Chord(s) => s.style(),
             DrawableShape::Rectangle(s) => s.style(),
+            DrawableShape::RoundedRect(s) => s.style(),
+            DrawableShape::Line(s) => s.style(),
+            DrawableShape::Polyline(s) => s.style(),
+            DrawableShape::Bezier(s) => s.style(),
+            DrawableShape::Polygon(s) => s.style(),
+            DrawableShape::Region(s) => s.style(),
         }
     }
 }
@@ -114,9 +501,63 @@ impl From<Square> for DrawableShape {
         if width == height { DrawableShape::Square(square) } else { DrawableShape::Rectangle(square) }
     }
 }
+impl From<RoundedRect> for DrawableShape {
+    fn from(rounded_rect: RoundedRect) -> Self { DrawableShape::RoundedRect(rounded_rect) }
+}
 impl From<Circle> for DrawableShape {
     fn from(circle: Circle) -> Self { DrawableShape::Circle(circle) }
 }
 impl From<Ellipse> for DrawableShape {
     fn from(ellipse: Ellipse) -> Self { DrawableShape::Ellipse(ellipse) }
 }
+impl From<Arc> for DrawableShape {
+    fn from(arc: Arc) -> Self {
+        match arc.read_fields().4 {
+            ArcKind::Open => DrawableShape::Arc(arc),
+            ArcKind::Pie => DrawableShape::Pie(arc),
+            ArcKind::Chord => DrawableShape::Chord(arc),
+        }
+    }
+}
+impl From<RotatedEllipse> for DrawableShape {
+    fn from(ellipse: RotatedEllipse) -> Self { DrawableShape::RotatedEllipse(ellipse) }
+}
+impl From<Line> for DrawableShape {
+    fn from(line: Line) -> Self { DrawableShape::Line(line) }
+}
+impl From<Polyline> for DrawableShape {
+    fn from(polyline: Polyline) -> Self { DrawableShape::Polyline(polyline) }
+}
+impl From<Bezier> for DrawableShape {
+    fn from(bezier: Bezier) -> Self { DrawableShape::Bezier(bezier) }
+}
+impl From<Polygon> for DrawableShape {
+    fn from(polygon: Polygon) -> Self { DrawableShape::Polygon(polygon) }
+}
+impl From<Region> for DrawableShape {
+    fn from(region: Region) -> Self { DrawableShape::Region(region) }
+}
+
+/// Vértice a la distancia `radius` del centro, en la dirección `angle` medida en sentido
+/// antihorario desde la vertical superior.
+fn polar_vertex(radius: f32, angle: f32) -> PixelCoord {
+    let (sin, cos) = angle.sin_cos();
+    ((-radius * sin).round() as i32, (-radius * cos).round() as i32)
+}
+
+/// Calcula el centro de la caja delimitadora de los puntos dados, y devuelve los puntos relativos a
+/// dicho centro.
+fn relative_to_bounding_box_center(points: &[PixelCoord]) -> (PixelCoord, Vec<PixelCoord>) {
+    use itertools::{Itertools, MinMaxResult};
+
+    let center_of = |values: MinMaxResult<i32>| {
+        match values {
+            MinMaxResult::NoElements => 0,
+            MinMaxResult::OneElement(v) => v,
+            MinMaxResult::MinMax(min, max) => (min + max) / 2,
+        }
+    };
+    let center = (center_of(points.iter().map(|p| p.0).minmax()), center_of(points.iter().map(|p| p.1).minmax()));
+    let relative = points.iter().map(|&(x, y)| (x - center.0, y - center.1)).collect();
+    (center, relative)
+}
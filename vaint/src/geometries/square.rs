@@ -1,4 +1,5 @@
-use super::ShapeStyle;
+use super::{Polygon, ShapeStyle, Transform};
+use crate::algorithms::{ClipRect, CoveragePoint};
 use crate::{PixelCoord, Shape};
 pub struct Square {
     width: u32,
@@ -22,6 +23,22 @@ impl Square {
 
     /// Modifica el estilo del cuadrado.
     pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada a las esquinas del rectángulo, el cual se
+    /// convierte en un polígono.
+    pub fn transformed(&self, transform: &Transform) -> Polygon {
+        let (width, height): (i32, i32) = (self.width as i32, self.height as i32);
+        let (x0, y0) = (-width / 2, -height / 2);
+        let corners = [(x0, y0), (x0 + width, y0), (x0 + width, y0 + height), (x0, y0 + height)];
+        Polygon::new(corners.map(|corner| transform.apply_linear_to_pixel(corner)).to_vec()).style(self.style)
+    }
+
+    /// Esquinas superior izquierda e inferior derecha del rectángulo centrado en el punto dado.
+    fn bounds(&self, center: PixelCoord) -> (PixelCoord, PixelCoord) {
+        let (width, height): (i32, i32) = (self.width as i32, self.height as i32);
+        let min = (center.0 - width / 2, center.1 - height / 2);
+        (min, (min.0 + width, min.1 + height))
+    }
 }
 
 impl Shape for Square {
@@ -50,5 +67,170 @@ impl Shape for Square {
         }
     }
 
+    /// Escribe al buffer dado los puntos visibles del contorno, recorriendo solo la parte de cada
+    /// lado que cruza el rectángulo de recorte.
+    fn write_clipped_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord, clip: &ClipRect) {
+        let (width, height): (i32, i32) = (self.width as i32, self.height as i32);
+        let (cx, cy) = (center.0 - width / 2, center.1 - height / 2);
+
+        let columns = cx.max(clip.x_min)..=(cx + width).min(clip.x_max);
+        for y in [cy, cy + height] {
+            if (clip.y_min..=clip.y_max).contains(&y) {
+                buf.extend(columns.clone().map(|x| (x, y)));
+            }
+        }
+        let rows = cy.max(clip.y_min)..=(cy + height).min(clip.y_max);
+        for x in [cx, cx + width] {
+            if (clip.x_min..=clip.x_max).contains(&x) {
+                buf.extend(rows.clone().map(|y| (x, y)));
+            }
+        }
+    }
+
+    /// Escribe al buffer dado los pixeles del borde del rectángulo, uniendo sus esquinas según
+    /// [`ShapeStyle::line_join`].
+    fn write_stroke_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (width, height): (i32, i32) = (self.width as i32, self.height as i32);
+        let (cx, cy) = (center.0 - width / 2, center.1 - height / 2);
+        let corners = [(cx, cy), (cx + width, cy), (cx + width, cy + height), (cx, cy + height)];
+        crate::algorithms::write_thick_path(&corners, true, self.style.stroke_width, self.style.line_join, buf);
+    }
+
+    /// Revisa si el punto dado está cerca de alguno de los lados. Los lados son horizontales y
+    /// verticales, por lo que sus pixeles están justo sobre el contorno ideal.
+    fn hit_outline_at(&self, (x, y): PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        let ((x0, y0), (x1, y1)) = self.bounds(center);
+        let distance = match ClipRect::new((x0, y0), (x1, y1)).contains((x, y)) {
+            true => (x - x0).min(x1 - x).min(y - y0).min(y1 - y) as f32,
+            false => ((x0 - x).max(x - x1).max(0) as f32).hypot((y0 - y).max(y - y1).max(0) as f32),
+        };
+        distance <= super::outline_reach(&self.style, tolerance)
+    }
+
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool {
+        let (min, max) = self.bounds(center);
+        ClipRect::new(min, max).contains(point)
+    }
+
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let (min, max) = self.bounds(center);
+        Some(super::outline_bounds(min, max, &self.style))
+    }
+
+    fn style(&self) -> &ShapeStyle { &self.style }
+}
+
+/// Rectángulo con las esquinas redondeadas por cuartos de circunferencia.
+pub struct RoundedRect {
+    width: u32,
+    height: u32,
+    radius: u32,
+    pub style: ShapeStyle,
+}
+
+impl RoundedRect {
+    /// Crea un nuevo rectángulo redondeado con el ancho, alto y radio de las esquinas dados.
+    ///
+    /// El radio se limita a la mitad del lado más corto, por lo que un cuadrado con un radio grande
+    /// es un círculo.
+    ///
+    /// # Debug Assertions
+    ///
+    /// Causa un pánico si los argumentos de anchura o altura saturan el espacio de [i32].
+    pub fn new(width: u32, height: u32, radius: u32) -> Self {
+        debug_assert!(width < i32::MAX as u32, "El ancho del rectángulo es demasiado grande");
+        debug_assert!(height < i32::MAX as u32, "El alto del rectángulo es demasiado grande");
+        Self { width, height, radius: radius.min(width.min(height) / 2), style: ShapeStyle::new() }
+    }
+
+    /// Lee los campos del objeto, y los devuelve como una tupla de (ancho, alto, radio, estilo).
+    pub fn read_fields(&self) -> (u32, u32, u32, ShapeStyle) { (self.width, self.height, self.radius, self.style) }
+
+    /// Modifica el estilo del rectángulo.
+    pub fn style(self, style: ShapeStyle) -> Self { Self { style, ..self } }
+
+    /// Aplica la parte lineal de la transformación dada al rectángulo, el cual se convierte en un
+    /// polígono que aproxima sus esquinas con segmentos de recta.
+    pub fn transformed(&self, transform: &Transform) -> Polygon {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        let ((x0, y0), (x1, y1)) = self.bounds((0, 0));
+        let r = self.radius as f32;
+        // Segmentos por esquina, de unos 2 pixeles de largo
+        let steps = ((r * FRAC_PI_2 / 2.0).ceil() as u32).max(1);
+        // Centro de cada esquina y ángulo en el que empieza su arco, en sentido horario
+        let corners = [
+            ((x0 as f32 + r, y0 as f32 + r), PI),
+            ((x1 as f32 - r, y0 as f32 + r), FRAC_PI_2),
+            ((x1 as f32 - r, y1 as f32 - r), 0.0),
+            ((x0 as f32 + r, y1 as f32 - r), -FRAC_PI_2),
+        ];
+        let mut vertices = Vec::with_capacity(4 * (steps as usize + 1));
+        for ((cx, cy), start) in corners {
+            for k in 0..=steps {
+                let (sin, cos) = (start - k as f32 * FRAC_PI_2 / steps as f32).sin_cos();
+                let (x, y) = transform.apply_linear((cx + r * cos, cy - r * sin));
+                vertices.push((x.round() as i32, y.round() as i32));
+            }
+        }
+        vertices.dedup();
+        Polygon::new(vertices).style(self.style)
+    }
+
+    /// Esquinas superior izquierda e inferior derecha del rectángulo centrado en el punto dado.
+    fn bounds(&self, center: PixelCoord) -> (PixelCoord, PixelCoord) {
+        let (width, height): (i32, i32) = (self.width as i32, self.height as i32);
+        let min = (center.0 - width / 2, center.1 - height / 2);
+        (min, (min.0 + width, min.1 + height))
+    }
+
+    /// Distancia del punto dado al contorno del rectángulo centrado en `center`, negativa dentro de
+    /// él.
+    fn distance(&self, (x, y): PixelCoord, center: PixelCoord) -> f32 {
+        let ((x0, y0), (x1, y1)) = self.bounds(center);
+        let r = self.radius as i32;
+        // Distancia al rectángulo formado por los centros de las esquinas
+        let (dx, dy) = ((x0 + r - x).max(x - x1 + r), (y0 + r - y).max(y - y1 + r));
+        let outside = (dx.max(0) as f32).hypot(dy.max(0) as f32);
+        outside + dx.max(dy).min(0) as f32 - r as f32
+    }
+}
+
+impl Shape for RoundedRect {
+    fn write_outline_points_at(&self, buf: &mut Vec<PixelCoord>, center: PixelCoord) {
+        let (min, max) = self.bounds(center);
+        crate::algorithms::write_rounded_rect_middle_point(min, max, self.radius as i32, buf);
+    }
+
+    /// Escribe al buffer dado los puntos del contorno con su cobertura. Las esquinas se dibujan con
+    /// [`crate::algorithms::write_wu_circle`], y los lados rectos con cobertura completa.
+    fn write_aa_outline_points_at(&self, buf: &mut Vec<CoveragePoint>, center: PixelCoord) {
+        let ((x0, y0), (x1, y1)) = self.bounds(center);
+        let r = self.radius as i32;
+        let corners =
+            [((x0 + r, y0 + r), (-1, -1)), ((x1 - r, y0 + r), (1, -1)), ((x1 - r, y1 - r), (1, 1)), ((x0 + r, y1 - r), (-1, 1))];
+        for ((cx, cy), (sx, sy)) in corners {
+            let mut corner = Vec::new();
+            crate::algorithms::write_wu_circle((cx, cy), r, &mut corner);
+            corner.retain(|&((x, y), _)| (x - cx) * sx >= 0 && (y - cy) * sy >= 0);
+            buf.append(&mut corner);
+        }
+        buf.extend((x0 + r..=x1 - r).flat_map(|x| [((x, y0), 1.0), ((x, y1), 1.0)]));
+        buf.extend((y0 + r..=y1 - r).flat_map(|y| [((x0, y), 1.0), ((x1, y), 1.0)]));
+    }
+
+    fn hit_outline_at(&self, point: PixelCoord, center: PixelCoord, tolerance: f32) -> bool {
+        super::hits_outline(self.distance(point, center), &self.style, tolerance)
+    }
+
+    /// Revisa si el punto dado es parte del relleno. Como en las elipses, se aceptan los puntos a
+    /// menos de medio pixel fuera de las esquinas.
+    fn contains_point_at(&self, point: PixelCoord, center: PixelCoord) -> bool { self.distance(point, center) <= 0.5 }
+
+    fn bounding_box_at(&self, center: PixelCoord) -> Option<ClipRect> {
+        let (min, max) = self.bounds(center);
+        Some(super::outline_bounds(min, max, &self.style))
+    }
+
     fn style(&self) -> &ShapeStyle { &self.style }
 }
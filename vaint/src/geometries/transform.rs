@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+/// Transformación afín 2D: una transformación lineal seguida de una traslación.
+///
+/// Un punto `p` se transforma en `linear · p + translation`. Como el eje Y de los pixeles apunta
+/// hacia abajo, los ángulos positivos giran en sentido antihorario en pantalla, igual que en
+/// [`crate::algorithms::arc_contains`].
+///
+/// Las transformaciones se combinan con [`Transform::then`]:
+///
+/// ```
+/// use vaint::geometries::Transform;
+///
+/// let transform = Transform::scale(2.0, 1.0).then(Transform::translate(10.0, 0.0));
+/// assert_eq!(transform.apply((1.0, 1.0)), (12.0, 1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Transform {
+    /// Matriz de la transformación lineal, por filas.
+    pub linear: [[f32; 2]; 2],
+    /// Desplazamiento aplicado después de la transformación lineal.
+    pub translation: (f32, f32),
+}
+
+impl Transform {
+    /// Transformación que deja todos los puntos en su lugar.
+    pub const IDENTITY: Self = Self { linear: [[1.0, 0.0], [0.0, 1.0]], translation: (0.0, 0.0) };
+
+    /// Traslación por el desplazamiento dado.
+    pub fn translate(dx: f32, dy: f32) -> Self { Self { translation: (dx, dy), ..Self::IDENTITY } }
+
+    /// Rotación alrededor del origen por el ángulo dado, en radianes y en sentido antihorario.
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { linear: [[cos, sin], [-sin, cos]], ..Self::IDENTITY }
+    }
+
+    /// Escalamiento respecto al origen por los factores dados en cada eje.
+    pub fn scale(sx: f32, sy: f32) -> Self { Self { linear: [[sx, 0.0], [0.0, sy]], ..Self::IDENTITY } }
+
+    /// Cizallamiento: `x` se desplaza `kx` veces `y`, y `y` se desplaza `ky` veces `x`.
+    pub fn shear(kx: f32, ky: f32) -> Self { Self { linear: [[1.0, kx], [ky, 1.0]], ..Self::IDENTITY } }
+
+    /// Reflexión respecto al eje X (invierte la coordenada `y`).
+    pub fn reflect_x() -> Self { Self::scale(1.0, -1.0) }
+
+    /// Reflexión respecto al eje Y (invierte la coordenada `x`).
+    pub fn reflect_y() -> Self { Self::scale(-1.0, 1.0) }
+
+    /// Combina esta transformación con `next`, aplicada después.
+    pub fn then(self, next: Self) -> Self {
+        let ([[a, b], [c, d]], [[e, f], [g, h]]) = (self.linear, next.linear);
+        Self {
+            linear: [[e * a + f * c, e * b + f * d], [g * a + h * c, g * b + h * d]],
+            translation: add(next.apply_linear(self.translation), next.translation),
+        }
+    }
+
+    /// Transformación inversa, o `None` si la transformación no es invertible (por ejemplo, un
+    /// escalamiento por 0).
+    pub fn inverse(&self) -> Option<Self> {
+        let [[a, b], [c, d]] = self.linear;
+        let det = a * d - b * c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let linear = [[d / det, -b / det], [-c / det, a / det]];
+        let inverse = Self { linear, ..Self::IDENTITY };
+        let (tx, ty) = inverse.apply_linear(self.translation);
+        Some(Self { translation: (-tx, -ty), ..inverse })
+    }
+
+    /// Aplica la transformación al punto dado.
+    pub fn apply(&self, point: (f32, f32)) -> (f32, f32) { add(self.apply_linear(point), self.translation) }
+
+    /// Aplica solo la parte lineal de la transformación al punto dado, sin trasladarlo.
+    pub fn apply_linear(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let [[a, b], [c, d]] = self.linear;
+        (a * x + b * y, c * x + d * y)
+    }
+
+    /// Aplica solo la parte lineal de la transformación al pixel dado, redondeando el resultado.
+    pub fn apply_linear_to_pixel(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        let (x, y) = self.apply_linear((x as f32, y as f32));
+        (x.round() as i32, y.round() as i32)
+    }
+
+    /// Revisa si la parte lineal de la transformación es la identidad, es decir, si la
+    /// transformación es solo una traslación.
+    pub fn is_translation(&self) -> bool { self.linear == Self::IDENTITY.linear }
+
+    /// Traslación de la transformación, redondeada al pixel más cercano.
+    pub fn pixel_translation(&self) -> (i32, i32) { (self.translation.0.round() as i32, self.translation.1.round() as i32) }
+}
+
+impl Default for Transform {
+    fn default() -> Self { Self::IDENTITY }
+}
+
+fn add(a: (f32, f32), b: (f32, f32)) -> (f32, f32) { (a.0 + b.0, a.1 + b.1) }
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::Transform;
+
+    #[track_caller]
+    fn assert_close(a: (f32, f32), b: (f32, f32)) {
+        assert!((a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn basic_transforms() {
+        // Un cuarto de vuelta antihorario lleva la derecha hacia arriba (Y negativa en pantalla)
+        assert_close(Transform::rotate(FRAC_PI_2).apply((1.0, 0.0)), (0.0, -1.0));
+        assert_close(Transform::scale(2.0, 3.0).apply((1.0, 1.0)), (2.0, 3.0));
+        assert_close(Transform::shear(0.5, 0.0).apply((0.0, 2.0)), (1.0, 2.0));
+        assert_close(Transform::reflect_x().apply((1.0, 2.0)), (1.0, -2.0));
+        assert_close(Transform::reflect_y().apply((1.0, 2.0)), (-1.0, 2.0));
+        assert_close(Transform::translate(3.0, -1.0).apply((1.0, 2.0)), (4.0, 1.0));
+    }
+
+    #[test]
+    fn composition_and_inverse() {
+        let transform = Transform::rotate(0.3).then(Transform::scale(2.0, 0.5)).then(Transform::translate(4.0, 5.0));
+        let point = (3.0, -7.0);
+        let step_by_step =
+            Transform::translate(4.0, 5.0).apply(Transform::scale(2.0, 0.5).apply(Transform::rotate(0.3).apply(point)));
+        assert_close(transform.apply(point), step_by_step);
+
+        let inverse = transform.inverse().unwrap();
+        assert_close(inverse.apply(transform.apply(point)), point);
+        assert_close(transform.then(inverse).apply(point), point);
+        assert!(Transform::scale(0.0, 1.0).inverse().is_none());
+        assert!(Transform::translate(1.0, 2.0).is_translation() && !Transform::shear(0.1, 0.0).is_translation());
+    }
+}
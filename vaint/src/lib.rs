@@ -5,14 +5,28 @@ use serde::{Deserialize, Serialize};
 
 #[path = "util/algorithms.rs"]
 pub mod algorithms;
+#[path = "app/canvas.rs"]
+pub mod canvas;
 pub mod geometries;
 #[path = "app/glium/mod.rs"]
 pub mod glium_app;
+#[path = "app/ipc.rs"]
+pub mod ipc;
 
 mod opengl;
+#[path = "app/raster.rs"]
+pub mod raster;
+#[path = "app/scene.rs"]
+pub mod scene;
 #[path = "util/tracing.rs"]
 pub mod tracing;
+#[path = "app/view.rs"]
+pub mod view;
 
+/// Configuración del panel de control, con una sola figura de cada tipo y un estilo compartido.
+///
+/// Es el formato antiguo de `config.json`; las escenas se guardan con [`scene::Scene`], que puede
+/// leer este formato con [`scene::Scene::from_config`].
 #[derive(Deserialize, Serialize)]
 pub struct Config {
     pub stroke_color: Color,
@@ -30,6 +44,43 @@ pub struct Config {
     pub centro_elipse: (i32, i32),
     pub radio_circulo: u32,
     pub centro_circulo: (i32, i32),
+    #[serde(default)]
+    pub inicio_linea: (i32, i32),
+    #[serde(default)]
+    pub fin_linea: (i32, i32),
+    #[serde(default)]
+    pub puntos_polilinea: Vec<(i32, i32)>,
+    #[serde(default)]
+    pub algoritmo_linea: algorithms::LineAlgorithm,
+    #[serde(default)]
+    pub antialiasing: bool,
+    #[serde(default)]
+    pub union_linea: algorithms::LineJoin,
+    #[serde(default)]
+    pub largo_rect_redondeado: u32,
+    #[serde(default)]
+    pub ancho_rect_redondeado: u32,
+    #[serde(default)]
+    pub radio_rect_redondeado: u32,
+    #[serde(default)]
+    pub centro_rect_redondeado: (i32, i32),
+    #[serde(default)]
+    pub lados_poligono: u32,
+    #[serde(default)]
+    pub radio_poligono: u32,
+    #[serde(default)]
+    pub centro_poligono: (i32, i32),
+    /// Rotación del polígono regular, en grados.
+    #[serde(default)]
+    pub rotacion_poligono: f32,
+    #[serde(default)]
+    pub puntas_estrella: u32,
+    #[serde(default)]
+    pub radio_exterior_estrella: u32,
+    #[serde(default)]
+    pub radio_interior_estrella: u32,
+    #[serde(default)]
+    pub centro_estrella: (i32, i32),
 }
 
 #[derive(Deserialize, Serialize)]
@@ -39,4 +90,9 @@ pub enum Figura {
     Rectangulo,
     Elipse,
     Cuadrado,
+    Linea,
+    Polilinea,
+    RectanguloRedondeado,
+    PoligonoRegular,
+    Estrella,
 }
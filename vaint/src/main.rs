@@ -1,6 +1,8 @@
-use std::fs;
-
 use eframe::{App, Frame, egui};
+use vaint::algorithms::{LineAlgorithm, LineJoin};
+use vaint::canvas::Canvas;
+use vaint::ipc::{GlWindowProcess, Message, scene_updates};
+use vaint::scene::{SCENE_PATH, Scene};
 use vaint::{Color, Figura};
 
 #[derive(PartialEq)]
@@ -10,6 +12,12 @@ enum ColorObjetivo {
     Fondo,
 }
 
+/// Ventana de OpenGL abierta desde el panel, con la última escena que se le envió.
+struct VentanaGl {
+    proceso: GlWindowProcess,
+    escena: Scene,
+}
+
 struct MiApp {
     stroke_color: Color,
     shape_background: Color,
@@ -27,7 +35,29 @@ struct MiApp {
     centro_elipse: (i32, i32),
     radio_circulo: u32,
     centro_circulo: (i32, i32),
+    inicio_linea: (i32, i32),
+    fin_linea: (i32, i32),
+    puntos_polilinea: Vec<(i32, i32)>,
+    algoritmo_linea: LineAlgorithm,
+    antialiasing: bool,
+    union_linea: LineJoin,
+    largo_rect_redondeado: u32,
+    ancho_rect_redondeado: u32,
+    radio_rect_redondeado: u32,
+    centro_rect_redondeado: (i32, i32),
+    lados_poligono: u32,
+    radio_poligono: u32,
+    centro_poligono: (i32, i32),
+    rotacion_poligono: f32,
+    puntas_estrella: u32,
+    radio_exterior_estrella: u32,
+    radio_interior_estrella: u32,
+    centro_estrella: (i32, i32),
     figuras_seleccionadas: Vec<Figura>,
+    ventana_gl: Option<VentanaGl>,
+    lienzo: Canvas,
+    /// Escena que se muestra en el lienzo.
+    escena_lienzo: Option<Scene>,
 }
 
 impl Default for MiApp {
@@ -46,7 +76,28 @@ impl Default for MiApp {
             centro_elipse: (300, 300),
             radio_circulo: 50,
             centro_circulo: (300, 300),
+            inicio_linea: (100, 100),
+            fin_linea: (400, 300),
+            puntos_polilinea: vec![(100, 400), (200, 300), (300, 400), (400, 300)],
+            algoritmo_linea: LineAlgorithm::Bresenham,
+            antialiasing: false,
+            union_linea: LineJoin::Miter,
+            largo_rect_redondeado: 60,
+            ancho_rect_redondeado: 90,
+            radio_rect_redondeado: 15,
+            centro_rect_redondeado: (300, 300),
+            lados_poligono: 6,
+            radio_poligono: 50,
+            centro_poligono: (300, 300),
+            rotacion_poligono: 0.0,
+            puntas_estrella: 5,
+            radio_exterior_estrella: 60,
+            radio_interior_estrella: 25,
+            centro_estrella: (300, 300),
             figuras_seleccionadas: vec![],
+            ventana_gl: None,
+            lienzo: Canvas::new(vec![], Color::from_u32_rgb(0xffffff)),
+            escena_lienzo: None,
             shape_background: Color::from_u32_rgb(0xffffff),
             screen_background: Color::from_u32_rgb(0xffffff),
         }
@@ -55,142 +106,316 @@ impl Default for MiApp {
 
 impl App for MiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let [r, g, b] = match &self.objetivo_color {
-                ColorObjetivo::Borde => &mut self.stroke_color,
-                ColorObjetivo::Relleno => &mut self.shape_background,
-                ColorObjetivo::Fondo => &mut self.screen_background,
-            }
-            .as_mut_slice();
-            ui.horizontal(|ui| {
-                ui.label("R:");
-                ui.add(egui::Slider::new(r, 0..=u8::MAX));
-            });
-            ui.horizontal(|ui| {
-                ui.label("G:");
-                ui.add(egui::Slider::new(g, 0..=u8::MAX));
-            });
-            ui.horizontal(|ui| {
-                ui.label("B:");
-                ui.add(egui::Slider::new(b, 0..=u8::MAX));
-            });
-            let color = egui::Color32::from_rgb(*r, *g, *b);
-
-            ui.add(egui::Button::new("                   ").fill(color));
-
-            ui.horizontal(|ui| {
-                ui.radio_value(&mut self.objetivo_color, ColorObjetivo::Borde, "Borde");
-                ui.radio_value(&mut self.objetivo_color, ColorObjetivo::Relleno, "Relleno");
-                ui.radio_value(&mut self.objetivo_color, ColorObjetivo::Fondo, "Fondo de Pantalla");
-            });
-
-            ui.horizontal(|ui: &mut egui::Ui| {
-                ui.label("Grosor:");
-                ui.add(egui::Slider::new(&mut self.grosor, 1.0..=50.0));
-            });
-
-            ui.group(|ui| {
-                for figura in &self.figuras_seleccionadas {
-                    match figura {
-                        Figura::Circulo => {
-                            ui.horizontal(|ui| {
-                                ui.label("Radio (Círculo):");
-                                ui.add(egui::DragValue::new(&mut self.radio_circulo));
-                                ui.label("Centro X:");
-                                ui.add(egui::DragValue::new(&mut self.centro_circulo.0));
-                                ui.label("Centro Y:");
-                                ui.add(egui::DragValue::new(&mut self.centro_circulo.1));
-                            });
-                        }
-                        Figura::Cuadrado => {
+        egui::SidePanel::left("controles").resizable(false).show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| self.controles(ui));
+        });
+
+        let escena = Scene::from_config(&self.config());
+        if self.escena_lienzo.as_ref() != Some(&escena) {
+            self.lienzo.set_shapes(escena.to_shapes(), escena.background_color);
+            self.escena_lienzo = Some(escena.clone());
+        }
+        egui::CentralPanel::default().frame(egui::Frame::NONE).show(ctx, |ui| self.lienzo.show(ui));
+
+        self.sincronizar_ventana_gl(escena);
+    }
+}
+
+impl MiApp {
+    /// Controles del panel: colores, estilo y parámetros de las figuras.
+    fn controles(&mut self, ui: &mut egui::Ui) {
+        let [r, g, b] = match &self.objetivo_color {
+            ColorObjetivo::Borde => &mut self.stroke_color,
+            ColorObjetivo::Relleno => &mut self.shape_background,
+            ColorObjetivo::Fondo => &mut self.screen_background,
+        }
+        .as_mut_slice();
+        ui.horizontal(|ui| {
+            ui.label("R:");
+            ui.add(egui::Slider::new(r, 0..=u8::MAX));
+        });
+        ui.horizontal(|ui| {
+            ui.label("G:");
+            ui.add(egui::Slider::new(g, 0..=u8::MAX));
+        });
+        ui.horizontal(|ui| {
+            ui.label("B:");
+            ui.add(egui::Slider::new(b, 0..=u8::MAX));
+        });
+        let color = egui::Color32::from_rgb(*r, *g, *b);
+
+        ui.add(egui::Button::new("                   ").fill(color));
+
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.objetivo_color, ColorObjetivo::Borde, "Borde");
+            ui.radio_value(&mut self.objetivo_color, ColorObjetivo::Relleno, "Relleno");
+            ui.radio_value(&mut self.objetivo_color, ColorObjetivo::Fondo, "Fondo de Pantalla");
+        });
+
+        ui.horizontal(|ui: &mut egui::Ui| {
+            ui.label("Grosor:");
+            ui.add(egui::Slider::new(&mut self.grosor, 1.0..=50.0));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Unión de bordes:");
+            ui.radio_value(&mut self.union_linea, LineJoin::Miter, "Inglete");
+            ui.radio_value(&mut self.union_linea, LineJoin::Round, "Redonda");
+            ui.radio_value(&mut self.union_linea, LineJoin::Bevel, "Bisel");
+        });
+
+        ui.checkbox(&mut self.antialiasing, "Antialiasing");
+
+        ui.group(|ui| {
+            for figura in &self.figuras_seleccionadas {
+                match figura {
+                    Figura::Circulo => {
+                        ui.horizontal(|ui| {
+                            ui.label("Radio (Círculo):");
+                            ui.add(egui::DragValue::new(&mut self.radio_circulo));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_circulo.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_circulo.1));
+                        });
+                    }
+                    Figura::Cuadrado => {
+                        ui.horizontal(|ui| {
+                            ui.label("Lado (Cuadrado):");
+                            ui.add(egui::DragValue::new(&mut self.cuadrado));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_cuadrado.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_cuadrado.1));
+                        });
+                    }
+                    Figura::Rectangulo => {
+                        ui.horizontal(|ui| {
+                            ui.label("Largo (Rectángulo):");
+                            ui.add(egui::DragValue::new(&mut self.largo_rectangulo));
+                            ui.label("Ancho:");
+                            ui.add(egui::DragValue::new(&mut self.ancho_rectangulo));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_rectangulo.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_rectangulo.1));
+                        });
+                    }
+                    Figura::RectanguloRedondeado => {
+                        ui.horizontal(|ui| {
+                            ui.label("Largo (Rect. redondeado):");
+                            ui.add(egui::DragValue::new(&mut self.largo_rect_redondeado));
+                            ui.label("Ancho:");
+                            ui.add(egui::DragValue::new(&mut self.ancho_rect_redondeado));
+                            ui.label("Radio:");
+                            ui.add(egui::DragValue::new(&mut self.radio_rect_redondeado));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_rect_redondeado.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_rect_redondeado.1));
+                        });
+                    }
+                    Figura::PoligonoRegular => {
+                        ui.horizontal(|ui| {
+                            ui.label("Lados (Polígono regular):");
+                            ui.add(egui::DragValue::new(&mut self.lados_poligono).range(3..=64));
+                            ui.label("Radio:");
+                            ui.add(egui::DragValue::new(&mut self.radio_poligono));
+                            ui.label("Rotación:");
+                            ui.add(egui::DragValue::new(&mut self.rotacion_poligono).suffix("°"));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_poligono.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_poligono.1));
+                        });
+                    }
+                    Figura::Estrella => {
+                        ui.horizontal(|ui| {
+                            ui.label("Puntas (Estrella):");
+                            ui.add(egui::DragValue::new(&mut self.puntas_estrella).range(2..=64));
+                            ui.label("Radio exterior:");
+                            ui.add(egui::DragValue::new(&mut self.radio_exterior_estrella));
+                            ui.label("Radio interior:");
+                            ui.add(egui::DragValue::new(&mut self.radio_interior_estrella));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_estrella.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_estrella.1));
+                        });
+                    }
+                    Figura::Elipse => {
+                        ui.horizontal(|ui| {
+                            ui.label("Radio 1 (Elipse):");
+                            ui.add(egui::DragValue::new(&mut self.radio1_elipse));
+                            ui.label("Radio 2:");
+                            ui.add(egui::DragValue::new(&mut self.radio2_elipse));
+                            ui.label("Centro X:");
+                            ui.add(egui::DragValue::new(&mut self.centro_elipse.0));
+                            ui.label("Centro Y:");
+                            ui.add(egui::DragValue::new(&mut self.centro_elipse.1));
+                        });
+                    }
+                    Figura::Linea => {
+                        ui.horizontal(|ui| {
+                            ui.label("Inicio X (Línea):");
+                            ui.add(egui::DragValue::new(&mut self.inicio_linea.0));
+                            ui.label("Inicio Y:");
+                            ui.add(egui::DragValue::new(&mut self.inicio_linea.1));
+                            ui.label("Fin X:");
+                            ui.add(egui::DragValue::new(&mut self.fin_linea.0));
+                            ui.label("Fin Y:");
+                            ui.add(egui::DragValue::new(&mut self.fin_linea.1));
+                        });
+                    }
+                    Figura::Polilinea => {
+                        ui.label("Puntos (Polilínea):");
+                        let mut eliminar = None;
+                        for (i, punto) in self.puntos_polilinea.iter_mut().enumerate() {
                             ui.horizontal(|ui| {
-                                ui.label("Lado (Cuadrado):");
-                                ui.add(egui::DragValue::new(&mut self.cuadrado));
-                                ui.label("Centro X:");
-                                ui.add(egui::DragValue::new(&mut self.centro_cuadrado.0));
-                                ui.label("Centro Y:");
-                                ui.add(egui::DragValue::new(&mut self.centro_cuadrado.1));
+                                ui.label(format!("Punto {}: X:", i + 1));
+                                ui.add(egui::DragValue::new(&mut punto.0));
+                                ui.label("Y:");
+                                ui.add(egui::DragValue::new(&mut punto.1));
+                                if ui.small_button("➖").clicked() {
+                                    eliminar = Some(i);
+                                }
                             });
                         }
-                        Figura::Rectangulo => {
-                            ui.horizontal(|ui| {
-                                ui.label("Largo (Rectángulo):");
-                                ui.add(egui::DragValue::new(&mut self.largo_rectangulo));
-                                ui.label("Ancho:");
-                                ui.add(egui::DragValue::new(&mut self.ancho_rectangulo));
-                                ui.label("Centro X:");
-                                ui.add(egui::DragValue::new(&mut self.centro_rectangulo.0));
-                                ui.label("Centro Y:");
-                                ui.add(egui::DragValue::new(&mut self.centro_rectangulo.1));
-                            });
+                        if let Some(i) = eliminar {
+                            self.puntos_polilinea.remove(i);
                         }
-                        Figura::Elipse => {
-                            ui.horizontal(|ui| {
-                                ui.label("Radio 1 (Elipse):");
-                                ui.add(egui::DragValue::new(&mut self.radio1_elipse));
-                                ui.label("Radio 2:");
-                                ui.add(egui::DragValue::new(&mut self.radio2_elipse));
-                                ui.label("Centro X:");
-                                ui.add(egui::DragValue::new(&mut self.centro_elipse.0));
-                                ui.label("Centro Y:");
-                                ui.add(egui::DragValue::new(&mut self.centro_elipse.1));
-                            });
+                        if ui.small_button("➕ Agregar punto").clicked() {
+                            let ultimo = self.puntos_polilinea.last().copied().unwrap_or((300, 300));
+                            self.puntos_polilinea.push((ultimo.0 + 50, ultimo.1));
                         }
                     }
                 }
-            });
-
-            ui.group(|ui| {
-                ui.label(" Figuras seleccionadas:");
-
-                for figura in [
-                    (Figura::Circulo, "🔵 Círculo"),
-                    (Figura::Rectangulo, "▭ Rectángulo"),
-                    (Figura::Cuadrado, "➖ Cuadrado"),
-                    (Figura::Elipse, "🟡 Elipse"),
-                ] {
-                    let mut selected = self.figuras_seleccionadas.contains(&figura.0);
-                    if ui.checkbox(&mut selected, figura.1).changed() {
-                        if selected {
-                            self.figuras_seleccionadas.push(figura.0.clone());
-                        } else {
-                            self.figuras_seleccionadas.retain(|f| f != &figura.0);
-                        }
+            }
+
+            if self.figuras_seleccionadas.iter().any(|f| matches!(f, Figura::Linea | Figura::Polilinea)) {
+                ui.horizontal(|ui| {
+                    ui.label("Algoritmo de línea:");
+                    ui.radio_value(&mut self.algoritmo_linea, LineAlgorithm::Dda, "DDA");
+                    ui.radio_value(&mut self.algoritmo_linea, LineAlgorithm::Bresenham, "Bresenham");
+                });
+            }
+        });
+
+        ui.group(|ui| {
+            ui.label(" Figuras seleccionadas:");
+
+            for figura in [
+                (Figura::Circulo, "🔵 Círculo"),
+                (Figura::Rectangulo, "▭ Rectángulo"),
+                (Figura::RectanguloRedondeado, "▢ Rectángulo redondeado"),
+                (Figura::PoligonoRegular, "⬡ Polígono regular"),
+                (Figura::Estrella, "⭐ Estrella"),
+                (Figura::Cuadrado, "➖ Cuadrado"),
+                (Figura::Elipse, "🟡 Elipse"),
+                (Figura::Linea, "📏 Línea"),
+                (Figura::Polilinea, "〰 Polilínea"),
+            ] {
+                let mut selected = self.figuras_seleccionadas.contains(&figura.0);
+                if ui.checkbox(&mut selected, figura.1).changed() {
+                    if selected {
+                        self.figuras_seleccionadas.push(figura.0.clone());
+                    } else {
+                        self.figuras_seleccionadas.retain(|f| f != &figura.0);
                     }
                 }
+            }
 
-                ui.label(format!("Figuras actuales: {:?}", self.figuras_seleccionadas));
-            });
-
-            if ui.button("🗑️ Iniciar Tablero").clicked() {
-                // Guarda la configuración actual
-                let config = vaint::Config {
-                    stroke_color: self.stroke_color,
-                    figuras: self.figuras_seleccionadas.clone(),
-                    grosor: self.grosor,
-                    cuadrado: self.cuadrado,
-                    centro_cuadrado: self.centro_cuadrado,
-                    largo_rectangulo: self.largo_rectangulo,
-                    ancho_rectangulo: self.ancho_rectangulo,
-                    centro_rectangulo: self.centro_rectangulo,
-                    radio1_elipse: self.radio1_elipse,
-                    radio2_elipse: self.radio2_elipse,
-                    centro_elipse: self.centro_elipse,
-                    radio_circulo: self.radio_circulo,
-                    centro_circulo: self.centro_circulo,
-                    shape_background_color: self.shape_background,
-                    background_color: self.screen_background,
-                };
-                fs::write("config.json", serde_json::to_string(&config).unwrap()).unwrap();
+            ui.label(format!("Figuras actuales: {:?}", self.figuras_seleccionadas));
+        });
 
+        // El lienzo muestra las figuras en esta ventana; la ventana de OpenGL permite editarlas
+        if ui.button("🖼 Abrir en OpenGL").clicked() {
+            // Guarda la configuración actual como una escena
+            let escena = Scene::from_config(&self.config());
+            if let Err(e) = escena.save(SCENE_PATH) {
+                tracing::error!("No se pudo guardar la escena en `{SCENE_PATH}`: {e}");
+            }
+            self.abrir_ventana_gl(escena);
+        }
+    }
+
+    /// Configuración actual del panel.
+    fn config(&self) -> vaint::Config {
+        vaint::Config {
+            stroke_color: self.stroke_color,
+            figuras: self.figuras_seleccionadas.clone(),
+            grosor: self.grosor,
+            cuadrado: self.cuadrado,
+            centro_cuadrado: self.centro_cuadrado,
+            largo_rectangulo: self.largo_rectangulo,
+            ancho_rectangulo: self.ancho_rectangulo,
+            centro_rectangulo: self.centro_rectangulo,
+            radio1_elipse: self.radio1_elipse,
+            radio2_elipse: self.radio2_elipse,
+            centro_elipse: self.centro_elipse,
+            radio_circulo: self.radio_circulo,
+            centro_circulo: self.centro_circulo,
+            inicio_linea: self.inicio_linea,
+            fin_linea: self.fin_linea,
+            puntos_polilinea: self.puntos_polilinea.clone(),
+            algoritmo_linea: self.algoritmo_linea,
+            antialiasing: self.antialiasing,
+            union_linea: self.union_linea,
+            largo_rect_redondeado: self.largo_rect_redondeado,
+            ancho_rect_redondeado: self.ancho_rect_redondeado,
+            radio_rect_redondeado: self.radio_rect_redondeado,
+            centro_rect_redondeado: self.centro_rect_redondeado,
+            lados_poligono: self.lados_poligono,
+            radio_poligono: self.radio_poligono,
+            centro_poligono: self.centro_poligono,
+            rotacion_poligono: self.rotacion_poligono,
+            puntas_estrella: self.puntas_estrella,
+            radio_exterior_estrella: self.radio_exterior_estrella,
+            radio_interior_estrella: self.radio_interior_estrella,
+            centro_estrella: self.centro_estrella,
+            shape_background_color: self.shape_background,
+            background_color: self.screen_background,
+        }
+    }
+
+    /// Abre la ventana de OpenGL, si no está abierta, y le envía la escena completa.
+    fn abrir_ventana_gl(&mut self, escena: Scene) {
+        let abierta = self.ventana_gl.take().and_then(|mut ventana| ventana.proceso.is_running().then_some(ventana.proceso));
+        let proceso = match abierta {
+            Some(proceso) => proceso,
+            None => {
                 println!("Lanzando ventana OpenGL como proceso externo...");
                 // la aplicación de OpenGL y Eframe ambos hacen uso de winit::event_loop::EventLoop, por lo que no
                 // es posible ejecutar la ventana de OpenGL en otro hilo.
                 //
                 // https://docs.rs/winit/latest/winit/event_loop/struct.EventLoopBuilder.html#method.build
-                let _ = std::process::Command::new("opengl_app.exe").spawn();
+                match GlWindowProcess::spawn() {
+                    Ok(proceso) => proceso,
+                    Err(e) => {
+                        tracing::error!("No se pudo abrir la ventana de OpenGL: {e}");
+                        return;
+                    }
+                }
             }
-        });
+        };
+        let mut ventana = VentanaGl { proceso, escena };
+        match ventana.proceso.send(&Message::LoadScene { scene: ventana.escena.clone() }) {
+            Ok(()) => self.ventana_gl = Some(ventana),
+            Err(e) => tracing::error!("No se pudo enviar la escena a la ventana de OpenGL: {e}"),
+        }
+    }
+
+    /// Envía a la ventana de OpenGL, si está abierta, los cambios del panel desde el último envío.
+    fn sincronizar_ventana_gl(&mut self, escena: Scene) {
+        let Some(mut ventana) = self.ventana_gl.take() else { return };
+        for message in scene_updates(&ventana.escena, &escena) {
+            if let Err(e) = ventana.proceso.send(&message) {
+                tracing::info!("La ventana de OpenGL se cerró: {e}");
+                return;
+            }
+        }
+        ventana.escena = escena;
+        self.ventana_gl = Some(ventana);
     }
 }
 
@@ -198,7 +423,7 @@ fn main() {
     vaint::tracing::init();
     tracing::info!("Vaint OpenGL App is running...");
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([495.0, 405.0]).with_position(egui::pos2(700.0, 350.0)),
+        viewport: egui::ViewportBuilder::default().with_inner_size([1280.0, 720.0]),
         ..Default::default()
     };
     let _: Result<(), eframe::Error> =
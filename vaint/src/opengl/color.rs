@@ -63,6 +63,10 @@ impl From<Color> for egui::Color32 {
     }
 }
 
+impl From<Color> for image::Rgb<u8> {
+    fn from(value: Color) -> Self { image::Rgb(value.0) }
+}
+
 impl From<(u8, u8, u8)> for Color {
     fn from((r, g, b): (u8, u8, u8)) -> Self { Color([r, g, b]) }
 }
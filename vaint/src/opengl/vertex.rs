@@ -12,9 +12,16 @@ pub type Rgb = [f32; 3];
 pub struct Vertex {
     pub position: [i32; 2],
     pub color: Rgb,
+    /// Fracción del pixel cubierta por el vértice, usada como transparencia (entre 0 y 1).
+    pub coverage: f32,
 }
-implement_vertex!(Vertex, position, color);
+implement_vertex!(Vertex, position, color, coverage);
 
 impl Vertex {
-    pub fn new(position: [i32; 2], color: impl Into<Color>) -> Self { Vertex { position, color: color.into().to_vec() } }
+    pub fn new(position: [i32; 2], color: impl Into<Color>) -> Self {
+        Vertex { position, color: color.into().to_vec(), coverage: 1.0 }
+    }
+
+    /// Modifica la cobertura del vértice.
+    pub fn coverage(self, coverage: f32) -> Self { Self { coverage, ..self } }
 }
@@ -1,4 +1,8 @@
 fn main() {
     vaint::tracing::init();
-    vaint::glium_app::run_loop_standalone();
+    // Iniciada por el panel de control, la ventana recibe la escena por la entrada estándar
+    match std::env::args().any(|arg| arg == vaint::ipc::IPC_FLAG) {
+        true => vaint::glium_app::run_loop_ipc(),
+        false => vaint::glium_app::run_loop_standalone(),
+    }
 }
@@ -1,5 +1,8 @@
 //! Algoritmos evaluados para el proyecto vaint
-use crate::PixelCoord;
+use image::{Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+
+use crate::{Color, PixelCoord};
 
 // /// Devuelve un vector de coordenadas de pixeles que representan una línea entre `p0` y `p` usando el algoritmo [DDA](https://en.wikipedia.org/wiki/Digital_differential_analyzer_(graphics_algorithm)).
 // pub fn dda(p0: PixelCoord, p: PixelCoord) -> Vec<PixelCoord> {
@@ -33,6 +36,474 @@ pub fn write_dda(p0: PixelCoord, p: PixelCoord, puntos: &mut Vec<PixelCoord>) {
     }
 }
 
+/// Escribe al buffer dado los puntos ubicados entre `p0` y `p1` usando el algoritmo de
+/// [Bresenham][algo], el cual solo utiliza aritmética entera.
+///
+/// Los puntos se escriben en orden desde `p0` hasta `p1`, y el conjunto de puntos es el mismo sin
+/// importar el sentido de la línea.
+///
+/// [algo]: https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+pub fn write_bresenham(p0: PixelCoord, p1: PixelCoord, puntos: &mut Vec<PixelCoord>) {
+    let start = puntos.len();
+    let (delta_x, delta_y) = (p1.0 - p0.0, p1.1 - p0.1);
+    // Usamos la implementación que nos dará la mayor cantidad de pasos en renderizado
+    let reversed = if delta_y.abs() <= delta_x.abs() { h_bresenham(p0, p1, puntos) } else { v_bresenham(p0, p1, puntos) };
+    if reversed {
+        puntos[start..].reverse();
+    }
+}
+
+/// Bresenham para líneas con pendiente `|m| <= 1`. Itera sobre el eje X de izquierda a derecha.
+///
+/// Devuelve `true` si los puntos fueron escritos desde `p1` hasta `p0`.
+fn h_bresenham(p0: PixelCoord, p1: PixelCoord, puntos: &mut Vec<PixelCoord>) -> bool {
+    let reversed = p1.0 < p0.0;
+    let ((x0, y0), (x1, y1)) = if reversed { (p1, p0) } else { (p0, p1) };
+
+    let (delta_x, delta_y) = (x1 - x0, (y1 - y0).abs());
+    let y_increment = if y0 < y1 { 1 } else { -1 };
+
+    let mut p: i32 = 2 * delta_y - delta_x;
+    let two_dy: i32 = 2 * delta_y;
+    let two_dy_dx: i32 = 2 * (delta_y - delta_x);
+
+    puntos.reserve_exact(delta_x as usize + 1);
+    let mut y = y0;
+    puntos.push((x0, y));
+    for x in (x0 + 1)..=x1 {
+        if p < 0 {
+            p += two_dy;
+        } else {
+            y += y_increment;
+            p += two_dy_dx;
+        }
+        puntos.push((x, y));
+    }
+    reversed
+}
+
+/// Bresenham para líneas con pendiente `|m| > 1`. Itera sobre el eje Y de arriba hacia abajo.
+///
+/// Devuelve `true` si los puntos fueron escritos desde `p1` hasta `p0`.
+fn v_bresenham(p0: PixelCoord, p1: PixelCoord, puntos: &mut Vec<PixelCoord>) -> bool {
+    let reversed = p1.1 < p0.1;
+    let ((x0, y0), (x1, y1)) = if reversed { (p1, p0) } else { (p0, p1) };
+
+    let (delta_x, delta_y) = ((x1 - x0).abs(), y1 - y0);
+    let x_increment = if x0 < x1 { 1 } else { -1 };
+
+    let mut p: i32 = 2 * delta_x - delta_y;
+    let two_dx: i32 = 2 * delta_x;
+    let two_dx_dy: i32 = 2 * (delta_x - delta_y);
+
+    puntos.reserve_exact(delta_y as usize + 1);
+    let mut x = x0;
+    puntos.push((x, y0));
+    for y in (y0 + 1)..=y1 {
+        if p < 0 {
+            p += two_dx;
+        } else {
+            x += x_increment;
+            p += two_dx_dy;
+        }
+        puntos.push((x, y));
+    }
+    reversed
+}
+
+/// Algoritmos disponibles para el trazado de líneas.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum LineAlgorithm {
+    /// Ver [`write_dda`].
+    Dda,
+    /// Ver [`write_bresenham`].
+    #[default]
+    Bresenham,
+}
+
+impl LineAlgorithm {
+    /// Escribe al buffer dado los puntos ubicados entre `p0` y `p1` usando el algoritmo
+    /// seleccionado.
+    pub fn write_line(self, p0: PixelCoord, p1: PixelCoord, puntos: &mut Vec<PixelCoord>) {
+        match self {
+            LineAlgorithm::Dda => write_dda(p0, p1, puntos),
+            LineAlgorithm::Bresenham => write_bresenham(p0, p1, puntos),
+        }
+    }
+
+    /// Escribe al buffer dado los puntos de la línea entre `p0` y `p1` que están dentro del
+    /// rectángulo de recorte.
+    ///
+    /// Los puntos escritos son los mismos que [`LineAlgorithm::write_line`] escribiría dentro del
+    /// rectángulo, en el mismo orden, pero solo se calculan los pasos de la línea que lo cruzan
+    /// (ver [`clip_line_liang_barsky`]).
+    pub fn write_line_clipped(self, p0: PixelCoord, p1: PixelCoord, clip: &ClipRect, puntos: &mut Vec<PixelCoord>) {
+        if clip.contains(p0) && clip.contains(p1) {
+            return self.write_line(p0, p1, puntos);
+        }
+        match self {
+            LineAlgorithm::Dda => write_dda_clipped(p0, p1, clip, puntos),
+            LineAlgorithm::Bresenham => write_bresenham_clipped(p0, p1, clip, puntos),
+        }
+    }
+}
+
+/// Rectángulo de recorte, con límites inclusivos en pixeles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipRect {
+    pub x_min: i32,
+    pub y_min: i32,
+    pub x_max: i32,
+    pub y_max: i32,
+}
+
+impl ClipRect {
+    /// Crea un rectángulo de recorte con los límites inclusivos dados.
+    pub fn new((x_min, y_min): PixelCoord, (x_max, y_max): PixelCoord) -> Self { Self { x_min, y_min, x_max, y_max } }
+
+    /// Crea el rectángulo de recorte de una pantalla con las dimensiones dadas, desde (0, 0) hasta
+    /// (ancho - 1, alto - 1).
+    pub fn from_dimensions((width, height): PixelCoord) -> Self { Self::new((0, 0), (width - 1, height - 1)) }
+
+    /// Revisa si el pixel dado está dentro del rectángulo.
+    pub fn contains(&self, (x, y): PixelCoord) -> bool {
+        (self.x_min..=self.x_max).contains(&x) && (self.y_min..=self.y_max).contains(&y)
+    }
+
+    /// Revisa si la caja delimitadora dada (con límites inclusivos) toca el rectángulo.
+    pub fn intersects(&self, (x_min, y_min): PixelCoord, (x_max, y_max): PixelCoord) -> bool {
+        x_min <= self.x_max && x_max >= self.x_min && y_min <= self.y_max && y_max >= self.y_min
+    }
+
+    /// Revisa si la caja delimitadora dada (con límites inclusivos) está completamente dentro del
+    /// rectángulo.
+    pub fn encloses(&self, min: PixelCoord, max: PixelCoord) -> bool { self.contains(min) && self.contains(max) }
+
+    /// Límites del rectángulo en coordenadas reales, extendidos en `margin` hacia cada lado.
+    fn bounds(&self, margin: f32) -> [f32; 4] {
+        [self.x_min as f32 - margin, self.y_min as f32 - margin, self.x_max as f32 + margin, self.y_max as f32 + margin]
+    }
+}
+
+/// Recorta el segmento entre `p0` y `p1` al rectángulo dado usando el algoritmo de
+/// [Cohen–Sutherland][algo].
+///
+/// Cada extremo se clasifica con un código de región de 4 bits (izquierda, derecha, arriba,
+/// abajo). El segmento se acepta si ambos códigos son cero, se descarta si comparten algún bit, y
+/// en otro caso se recorta el extremo exterior contra uno de los bordes y se repite.
+///
+/// Devuelve `None` si el segmento no cruza el rectángulo.
+///
+/// [algo]: https://en.wikipedia.org/wiki/Cohen%E2%80%93Sutherland_algorithm
+pub fn clip_line_cohen_sutherland(p0: (f32, f32), p1: (f32, f32), clip: &ClipRect) -> Option<((f32, f32), (f32, f32))> {
+    const LEFT: u8 = 0b0001;
+    const RIGHT: u8 = 0b0010;
+    const TOP: u8 = 0b0100;
+    const BOTTOM: u8 = 0b1000;
+
+    let [x_min, y_min, x_max, y_max] = clip.bounds(0.0);
+    let outcode = |(x, y): (f32, f32)| -> u8 {
+        let mut code = 0;
+        if x < x_min {
+            code |= LEFT;
+        } else if x > x_max {
+            code |= RIGHT;
+        }
+        if y < y_min {
+            code |= TOP;
+        } else if y > y_max {
+            code |= BOTTOM;
+        }
+        code
+    };
+
+    let (mut p0, mut p1) = (p0, p1);
+    let (mut code0, mut code1) = (outcode(p0), outcode(p1));
+    loop {
+        if code0 | code1 == 0 {
+            return Some((p0, p1));
+        }
+        if code0 & code1 != 0 {
+            return None;
+        }
+
+        // Se recorta el extremo que está fuera del rectángulo
+        let outside = if code0 != 0 { code0 } else { code1 };
+        let ((x0, y0), (x1, y1)) = (p0, p1);
+        let point = if outside & TOP != 0 {
+            (x0 + (x1 - x0) * (y_min - y0) / (y1 - y0), y_min)
+        } else if outside & BOTTOM != 0 {
+            (x0 + (x1 - x0) * (y_max - y0) / (y1 - y0), y_max)
+        } else if outside & RIGHT != 0 {
+            (x_max, y0 + (y1 - y0) * (x_max - x0) / (x1 - x0))
+        } else {
+            (x_min, y0 + (y1 - y0) * (x_min - x0) / (x1 - x0))
+        };
+
+        if outside == code0 {
+            p0 = point;
+            code0 = outcode(p0);
+        } else {
+            p1 = point;
+            code1 = outcode(p1);
+        }
+    }
+}
+
+/// Recorta el segmento entre `p0` y `p1` al rectángulo dado usando el algoritmo de
+/// [Liang–Barsky][algo].
+///
+/// El segmento se expresa en forma paramétrica `p0 + t (p1 - p0)`, y cada borde del rectángulo
+/// acota el intervalo de `t` visible, sin calcular intersecciones intermedias.
+///
+/// Devuelve `None` si el segmento no cruza el rectángulo.
+///
+/// [algo]: https://en.wikipedia.org/wiki/Liang%E2%80%93Barsky_algorithm
+pub fn clip_line_liang_barsky(p0: (f32, f32), p1: (f32, f32), clip: &ClipRect) -> Option<((f32, f32), (f32, f32))> {
+    let (t0, t1) = liang_barsky(p0, p1, clip.bounds(0.0))?;
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    Some(((p0.0 + t0 * dx, p0.1 + t0 * dy), (p0.0 + t1 * dx, p0.1 + t1 * dy)))
+}
+
+/// Intervalo del parámetro `t` del segmento `p0 + t (p1 - p0)` que queda dentro de los límites
+/// dados (`[x_min, y_min, x_max, y_max]`).
+fn liang_barsky(p0: (f32, f32), p1: (f32, f32), [x_min, y_min, x_max, y_max]: [f32; 4]) -> Option<(f32, f32)> {
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    // (p, q) por cada borde: izquierdo, derecho, superior, inferior
+    for (p, q) in [(-dx, p0.0 - x_min), (dx, x_max - p0.0), (-dy, p0.1 - y_min), (dy, y_max - p0.1)] {
+        if p == 0.0 {
+            // Segmento paralelo al borde, y fuera de él
+            if q < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = q / p;
+        if p < 0.0 {
+            t0 = t0.max(t);
+        } else {
+            t1 = t1.min(t);
+        }
+        if t0 > t1 {
+            return None;
+        }
+    }
+    Some((t0, t1))
+}
+
+/// Recorta el polígono `subject` contra el polígono convexo `clip` usando el algoritmo de
+/// [Sutherland–Hodgman][algo].
+///
+/// El polígono se recorta contra cada lado de `clip` por separado: en cada pasada se conservan los
+/// vértices del lado interior y se agregan las intersecciones de las aristas que cruzan el lado.
+/// Los vértices de `clip` pueden estar en cualquier sentido de giro.
+///
+/// Devuelve un polígono vacío si no hay intersección.
+///
+/// # Nota
+///
+/// Si `subject` es cóncavo y el resultado tiene varias partes, estas quedan unidas por aristas
+/// sobre los lados de `clip`.
+///
+/// [algo]: https://en.wikipedia.org/wiki/Sutherland%E2%80%93Hodgman_algorithm
+pub fn clip_polygon_sutherland_hodgman(subject: &[(f32, f32)], clip: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    use itertools::Itertools;
+
+    if clip.len() < 3 {
+        return Vec::new();
+    }
+    // Sentido de giro de `clip`, para saber de qué lado de cada arista está el interior
+    let orientation = clip.iter().circular_tuple_windows().map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum::<f32>().signum();
+    let side =
+        |(a, b): ((f32, f32), (f32, f32)), p: (f32, f32)| orientation * ((b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0));
+
+    let mut output = subject.to_vec();
+    for edge in clip.iter().copied().circular_tuple_windows() {
+        let input = std::mem::take(&mut output);
+        for (&previous, &current) in input.iter().circular_tuple_windows() {
+            let (d_previous, d_current) = (side(edge, previous), side(edge, current));
+            if d_current >= 0.0 {
+                if d_previous < 0.0 {
+                    output.push(lerp(previous, current, d_previous / (d_previous - d_current)));
+                }
+                output.push(current);
+            } else if d_previous >= 0.0 {
+                output.push(lerp(previous, current, d_previous / (d_previous - d_current)));
+            }
+        }
+        // Los vértices sobre la arista se agregan dos veces
+        output.dedup();
+        if output.len() > 1 && output.first() == output.last() {
+            output.pop();
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+/// Punto a una fracción `t` del camino entre `a` y `b`.
+fn lerp(a: (f32, f32), b: (f32, f32), t: f32) -> (f32, f32) { (a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1)) }
+
+/// Región convexa de recorte, definida por sus vértices en pixeles.
+///
+/// Un pixel es visible si su centro está dentro de la región o sobre su borde.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipRegion {
+    vertices: Vec<PixelCoord>,
+    /// Sentido de giro de los vértices (1 o -1)
+    orientation: i64,
+}
+
+impl ClipRegion {
+    /// Crea una región de recorte con los vértices dados.
+    ///
+    /// Devuelve `None` si hay menos de tres vértices distintos, si todos son colineales o si el
+    /// polígono no es convexo. Los vértices repetidos seguidos se descartan.
+    pub fn new(mut vertices: Vec<PixelCoord>) -> Option<Self> {
+        use itertools::Itertools;
+
+        vertices.dedup();
+        while vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        if vertices.len() < 3 {
+            return None;
+        }
+
+        let edges = vertices.iter().circular_tuple_windows().map(|(&a, &b)| (b.0 - a.0, b.1 - a.1)).collect::<Vec<_>>();
+        let turns = edges.iter().circular_tuple_windows().map(|(&u, &v)| cross_i64(u, v).signum()).filter(|&turn| turn != 0);
+        let orientation = turns.clone().next()?;
+        if turns.into_iter().any(|turn| turn != orientation) {
+            return None;
+        }
+
+        // Un polígono estrellado (que se cruza a sí mismo) también gira siempre hacia el mismo lado,
+        // pero da más de una vuelta: solo un polígono convexo gira en total 2π
+        let winding: f64 = edges
+            .iter()
+            .circular_tuple_windows()
+            .map(|(&u, &v)| (cross_i64(u, v) as f64).atan2(u.0 as f64 * v.0 as f64 + u.1 as f64 * v.1 as f64))
+            .sum();
+        if (winding.abs() - std::f64::consts::TAU).abs() > std::f64::consts::PI {
+            return None;
+        }
+        Some(Self { vertices, orientation })
+    }
+
+    /// Devuelve los vértices de la región.
+    pub fn vertices(&self) -> &[PixelCoord] { &self.vertices }
+
+    /// Revisa si el pixel dado está dentro de la región o sobre su borde.
+    pub fn contains(&self, (x, y): PixelCoord) -> bool {
+        use itertools::Itertools;
+
+        self.vertices
+            .iter()
+            .circular_tuple_windows()
+            .all(|(&a, &b)| self.orientation * cross_i64((b.0 - a.0, b.1 - a.1), (x - a.0, y - a.1)) >= 0)
+    }
+
+    /// Caja delimitadora de la parte de la región que queda dentro del rectángulo dado, calculada
+    /// con [`clip_polygon_sutherland_hodgman`].
+    ///
+    /// Devuelve `None` si la región y el rectángulo no se intersecan.
+    pub fn visible_bounds(&self, rect: &ClipRect) -> Option<ClipRect> {
+        use itertools::{Itertools, MinMaxResult};
+
+        let [x_min, y_min, x_max, y_max] = rect.bounds(0.0);
+        let rect_polygon = [(x_min, y_min), (x_max, y_min), (x_max, y_max), (x_min, y_max)];
+        let region = self.vertices.iter().map(|&(x, y)| (x as f32, y as f32)).collect::<Vec<_>>();
+        let visible = clip_polygon_sutherland_hodgman(&region, &rect_polygon);
+
+        let (MinMaxResult::MinMax(x0, x1), MinMaxResult::MinMax(y0, y1)) =
+            (visible.iter().map(|p| p.0).minmax_by(f32::total_cmp), visible.iter().map(|p| p.1).minmax_by(f32::total_cmp))
+        else {
+            return None;
+        };
+        Some(ClipRect::new((x0.ceil() as i32, y0.ceil() as i32), (x1.floor() as i32, y1.floor() as i32)))
+    }
+}
+
+impl From<ClipRect> for ClipRegion {
+    fn from(rect: ClipRect) -> Self {
+        let ClipRect { x_min, y_min, x_max, y_max } = rect;
+        Self { vertices: vec![(x_min, y_min), (x_max, y_min), (x_max, y_max), (x_min, y_max)], orientation: 1 }
+    }
+}
+
+/// Producto cruz de dos vectores enteros, sin desbordamiento.
+fn cross_i64(a: PixelCoord, b: PixelCoord) -> i64 { a.0 as i64 * b.1 as i64 - a.1 as i64 * b.0 as i64 }
+
+/// Intervalo de pasos (de 0 a `steps`) de la línea entre `p0` y `p1` que pueden quedar dentro del
+/// rectángulo, con un margen de un pixel para cubrir el redondeo de los algoritmos.
+fn visible_steps(p0: PixelCoord, p1: PixelCoord, steps: i32, clip: &ClipRect) -> Option<(i32, i32)> {
+    let (p0, p1) = ((p0.0 as f32, p0.1 as f32), (p1.0 as f32, p1.1 as f32));
+    let (t0, t1) = liang_barsky(p0, p1, clip.bounds(1.0))?;
+    let first = ((t0 * steps as f32).floor() as i32 - 1).max(0);
+    let last = ((t1 * steps as f32).ceil() as i32 + 1).min(steps);
+    Some((first, last))
+}
+
+/// Variante de [`write_dda`] que solo calcula los pasos de la línea cercanos al rectángulo.
+fn write_dda_clipped(p0: PixelCoord, p: PixelCoord, clip: &ClipRect, puntos: &mut Vec<PixelCoord>) {
+    let (x_0, y_0) = p0;
+    let (delta_x, delta_y) = (p.0 - x_0, p.1 - y_0);
+    let steps = i32::max(delta_x.abs(), delta_y.abs());
+    let Some((first, last)) = visible_steps(p0, p, steps, clip) else { return };
+
+    // Mismos cálculos que `write_dda`, para obtener exactamente los mismos puntos
+    let dx = (delta_x as f32) / steps as f32;
+    let dy = (delta_y as f32) / steps as f32;
+    for k in first..=last {
+        let k: f32 = k as f32;
+        let point = (x_0 + (dx * k).round() as i32, y_0 + (dy * k).round() as i32);
+        // Los pasos calculados incluyen un margen alrededor del rectángulo
+        if clip.contains(point) {
+            puntos.push(point);
+        }
+    }
+}
+
+/// Variante de [`write_bresenham`] que solo calcula los pasos de la línea cercanos al rectángulo.
+///
+/// El desplazamiento sobre el eje menor tras `k` pasos se calcula directamente como
+/// `(2 dy k + dx) / (2 dx)` (división entera), que es el mismo que acumula el parámetro de
+/// decisión de Bresenham.
+fn write_bresenham_clipped(p0: PixelCoord, p1: PixelCoord, clip: &ClipRect, puntos: &mut Vec<PixelCoord>) {
+    let (delta_x, delta_y) = (p1.0 - p0.0, p1.1 - p0.1);
+    let horizontal = delta_y.abs() <= delta_x.abs();
+    // Igual que `h_bresenham` y `v_bresenham`, se avanza desde el extremo menor del eje mayor
+    let reversed = if horizontal { p1.0 < p0.0 } else { p1.1 < p0.1 };
+    let (start, end) = if reversed { (p1, p0) } else { (p0, p1) };
+    let (major, minor) = if horizontal { (delta_x.abs(), delta_y.abs()) } else { (delta_y.abs(), delta_x.abs()) };
+    // Una línea de longitud cero es un solo punto, y no tiene pasos para dividir
+    if major == 0 {
+        if clip.contains(p0) {
+            puntos.push(p0);
+        }
+        return;
+    }
+    let Some((first, last)) = visible_steps(start, end, major, clip) else { return };
+
+    let increment = if horizontal { (end.1 - start.1).signum() } else { (end.0 - start.0).signum() };
+    let offset = |k: i32| -> i32 { ((2 * minor as i64 * k as i64 + major as i64) / (2 * major as i64)) as i32 };
+    let first_point = puntos.len();
+    for k in first..=last {
+        let across = increment * offset(k);
+        let point = if horizontal { (start.0 + k, start.1 + across) } else { (start.0 + across, start.1 + k) };
+        // Los pasos calculados incluyen un margen alrededor del rectángulo
+        if clip.contains(point) {
+            puntos.push(point);
+        }
+    }
+    if reversed {
+        puntos[first_point..].reverse();
+    }
+}
+
 /// Estructura auxiliar para el dibujo de un círculo.
 #[repr(transparent)]
 struct Circulo(PixelCoord);
@@ -60,26 +531,46 @@ impl Circulo {
 /// Escribe al buffer dado los puntos ubicados en la circunferencia del círculo usando el algoritmo
 /// de CPM (Circulo de Punto Medio).
 pub fn write_circle_middle_point(centro: PixelCoord, r: i32, puntos: &mut Vec<PixelCoord>) {
-    let (cx, cy) = centro;
+    // Se insertan los puntos de cada octante
+    circle_octant(r, |point| puntos.extend(CIRCLE_OCTANTS.map(|(reflection, _)| reflect(centro, point, reflection))));
+}
+
+/// Reflexión de un punto generado en el primer octante (o cuadrante) hacia otro: signo de cada
+/// eje, y si se intercambian los ejes.
+type Reflection = (i32, i32, bool);
+
+/// Reflexiones de [`circle_octant`] hacia los ocho octantes, junto con el octante al que llevan: el
+/// octante `k` cubre los ángulos de `k·π/4` a `(k + 1)·π/4` (ver [`arc_contains`]).
+const CIRCLE_OCTANTS: [(Reflection, u8); 8] = [
+    ((1, 1, false), 6),
+    ((-1, 1, false), 5),
+    ((1, -1, false), 1),
+    ((-1, -1, false), 2),
+    ((1, 1, true), 7),
+    ((-1, 1, true), 4),
+    ((1, -1, true), 0),
+    ((-1, -1, true), 3),
+];
+
+/// Reflexiones de [`ellipse_quadrant`] hacia los cuatro cuadrantes, junto con el cuadrante al que
+/// llevan: el cuadrante `k` cubre los ángulos de `k·π/2` a `(k + 1)·π/2`.
+const ELLIPSE_QUADRANTS: [(Reflection, u8); 4] =
+    [((1, 1, false), 3), ((-1, 1, false), 2), ((1, -1, false), 0), ((-1, -1, false), 1)];
+
+/// Refleja el punto dado, relativo al centro, y lo lleva a coordenadas de pantalla.
+fn reflect((cx, cy): PixelCoord, (x, y): PixelCoord, (sx, sy, swap): Reflection) -> PixelCoord {
+    let (x, y) = if swap { (y, x) } else { (x, y) };
+    (cx + sx * x, cy + sy * y)
+}
+
+/// Genera con el algoritmo de CPM los puntos de un octante del círculo de radio `r` centrado en el
+/// origen: desde `(0, r)` hasta la diagonal, con `0 <= x <= y` (salvo el último paso).
+fn circle_octant(r: i32, mut plot: impl FnMut(PixelCoord)) {
     let mut c = Circulo::new();
     c.set_coords((0, r));
     let mut d = 1 - r;
 
-    let mut plot_circle = |c: &Circulo| {
-        // Se insertan los puntos iniciales para cada octante
-        puntos.extend([
-            (cx + c.x(), cy + c.y()),
-            (cx - c.x(), cy + c.y()),
-            (cx + c.x(), cy - c.y()),
-            (cx - c.x(), cy - c.y()),
-            (cx + c.y(), cy + c.x()),
-            (cx - c.y(), cy + c.x()),
-            (cx + c.y(), cy - c.x()),
-            (cx - c.y(), cy - c.x()),
-        ]);
-    };
-
-    plot_circle(&c);
+    plot(c.0);
     let mut circulo = c;
     while circulo.x() < circulo.y() {
         *circulo.x_mut() += 1;
@@ -89,32 +580,27 @@ pub fn write_circle_middle_point(centro: PixelCoord, r: i32, puntos: &mut Vec<Pi
             *circulo.y_mut() -= 1;
             d += 2 * (circulo.x() - circulo.y()) + 1;
         }
-        plot_circle(&circulo);
+        plot(circulo.0);
     }
 }
 
 /// Escribe al buffer dado los puntos ubicados en la circunferencia de una elipse usando el
 /// algoritmo de EPM (Elipse de Punto Medio).
 pub fn write_ellipse_middle_point(centro: PixelCoord, rx: i32, ry: i32, puntos: &mut Vec<PixelCoord>) {
+    ellipse_quadrant(rx, ry, |point| puntos.extend(ELLIPSE_QUADRANTS.map(|(reflection, _)| reflect(centro, point, reflection))));
+}
+
+/// Genera con el algoritmo de EPM los puntos de un cuadrante de la elipse de radios `rx` y `ry`
+/// centrada en el origen: desde `(0, ry)` hasta `(rx, 0)`.
+fn ellipse_quadrant(rx: i32, ry: i32, mut plot: impl FnMut(PixelCoord)) {
     let (rx2, ry2) = (rx.pow(2), ry.pow(2));
     let (two_rx2, two_ry2) = (2 * rx2, 2 * ry2);
-    let (cx, cy) = centro;
 
     let round = |arg: f32| -> i32 { (arg + 0.5) as i32 };
-    // Función auxiliar para registrar los puntos de la elipse en el vector
-    let mut plot_elipse = |x: i32, y: i32| {
-        #[rustfmt::skip]
-        puntos.extend([
-            (cx + x, cy + y),
-            (cx - x, cy + y),
-            (cx + x, cy - y),
-            (cx - x, cy - y),
-        ]);
-    };
 
     let (mut x, mut y) = (0, ry);
 
-    plot_elipse(x, y);
+    plot((x, y));
 
     let (mut px, mut py) = (0, two_rx2 * y);
     let mut p = round(ry2 as f32 - (rx2 * y) as f32 + (0.25 * rx2 as f32));
@@ -130,7 +616,7 @@ pub fn write_ellipse_middle_point(centro: PixelCoord, rx: i32, ry: i32, puntos:
             py -= two_rx2;
             p += ry2 + px - py;
         }
-        plot_elipse(x, y);
+        plot((x, y));
     }
 
     // Región 2
@@ -149,38 +635,2169 @@ pub fn write_ellipse_middle_point(centro: PixelCoord, rx: i32, ry: i32, puntos:
             px += two_ry2;
             p += rx2 - py + px;
         }
-        plot_elipse(x, y);
+        plot((x, y));
     }
 }
 
-/// Escribe al buffer dado los puntos que forman el relleno del objeto.
-pub fn flood_fill(outline_points: &[PixelCoord], buf: &mut Vec<PixelCoord>) {
-    use itertools::Itertools;
+/// Escribe al buffer dado los puntos de una elipse de radios `rx` y `ry`, girada `angle` radianes
+/// en sentido antihorario (ver [`write_affine_ellipse`]).
+pub fn write_rotated_ellipse(centro: PixelCoord, rx: f32, ry: f32, angle: f32, puntos: &mut Vec<PixelCoord>) {
+    let (sin, cos) = angle.sin_cos();
+    write_affine_ellipse(centro, [(rx * cos, -rx * sin), (ry * sin, ry * cos)], puntos);
+}
 
-    if outline_points.is_empty() {
+/// Escribe al buffer dado los puntos de la elipse formada por los puntos `centro + u cos t + v sin
+/// t`, donde `u` y `v` son dos semiejes conjugados. Es la imagen de una circunferencia bajo
+/// cualquier transformación afín, por lo que sus ejes pueden tener cualquier dirección.
+///
+/// La elipse se describe con su ecuación implícita `A x² + B xy + C y² = 1`. En cada fila y cada
+/// columna dentro de la elipse se resuelve la ecuación cuadrática y se redondean sus dos
+/// soluciones, por lo que el contorno no tiene huecos sin importar la inclinación de los ejes.
+///
+/// Si `u` y `v` son paralelos, la elipse degenera en un segmento.
+pub fn write_affine_ellipse(centro: PixelCoord, [u, v]: [(f32, f32); 2], puntos: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let Some(conic) = EllipseConic::new(u, v) else {
+        let (dx, dy) = degenerate_ellipse_extent(u, v);
+        let (dx, dy) = (dx.round() as i32, dy.round() as i32);
+        write_bresenham((cx - dx, cy - dy), (cx + dx, cy + dy), puntos);
         return;
+    };
+
+    let mut outline = Vec::new();
+    let (half_width, half_height) = (conic.half_width.floor() as i32, conic.half_height.floor() as i32);
+    for dy in -half_height..=half_height {
+        if let Some((x0, x1)) = conic.roots(conic.a, conic.c, dy as f32) {
+            outline.extend([(cx + x0.round() as i32, cy + dy), (cx + x1.round() as i32, cy + dy)]);
+        }
+    }
+    for dx in -half_width..=half_width {
+        if let Some((y0, y1)) = conic.roots(conic.c, conic.a, dx as f32) {
+            outline.extend([(cx + dx, cy + y0.round() as i32), (cx + dx, cy + y1.round() as i32)]);
+        }
     }
+    // Las soluciones de filas y columnas coinciden en las partes diagonales
+    outline.sort_unstable();
+    outline.dedup();
+    puntos.append(&mut outline);
+}
 
-    let copied_outline = outline_points.iter().sorted_by_key(|(_, y)| *y).collect::<Vec<_>>();
-    let bottom_y = copied_outline.last().unwrap().1;
-    for &(_, y) in copied_outline.iter().copied() {
-        if y > bottom_y {
-            break;
+/// Escribe al buffer dado los puntos interiores de la elipse de [`write_affine_ellipse`], fila por
+/// fila entre las dos soluciones de la ecuación de la elipse.
+///
+/// Una elipse degenerada no tiene relleno.
+pub fn write_affine_ellipse_fill(centro: PixelCoord, [u, v]: [(f32, f32); 2], buf: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let Some(conic) = EllipseConic::new(u, v) else { return };
+
+    let half_height = conic.half_height.floor() as i32;
+    for dy in -half_height..=half_height {
+        if let Some((x0, x1)) = conic.roots(conic.a, conic.c, dy as f32) {
+            buf.extend((x0.round() as i32..=x1.round() as i32).map(|dx| (cx + dx, cy + dy)));
         }
-        #[rustfmt::skip]
-        let Some((min, max)) =
-            copied_outline.iter()
-            .filter(|(_, current_y)| *current_y == y)
-            .map(|(x, _)| *x)
-            .minmax()
-            .into_option()
-        else {
-            tracing::error!("Unexpected None");
-            continue;
+    }
+}
+
+/// Ecuación implícita `a x² + b xy + c y² = 1` de una elipse centrada en el origen.
+struct EllipseConic {
+    a: f32,
+    b: f32,
+    c: f32,
+    /// Distancia horizontal máxima del centro a la elipse
+    half_width: f32,
+    /// Distancia vertical máxima del centro a la elipse
+    half_height: f32,
+}
+
+impl EllipseConic {
+    /// Calcula la ecuación de la elipse de semiejes conjugados `u` y `v`, o `None` si la elipse es
+    /// degenerada.
+    ///
+    /// Los puntos de la elipse son `M (cos t, sin t)`, con `M = [u v]`, por lo que cumplen
+    /// `|M⁻¹ p|² = 1`.
+    fn new(u: (f32, f32), v: (f32, f32)) -> Option<Self> {
+        let det = u.0 * v.1 - v.0 * u.1;
+        if det.abs() <= f32::EPSILON * (dot(u, u) + dot(v, v)) {
+            return None;
+        }
+        let det2 = det * det;
+        Some(Self {
+            a: (u.1 * u.1 + v.1 * v.1) / det2,
+            b: -2.0 * (u.0 * u.1 + v.0 * v.1) / det2,
+            c: (u.0 * u.0 + v.0 * v.0) / det2,
+            half_width: u.0.hypot(v.0),
+            half_height: u.1.hypot(v.1),
+        })
+    }
+
+    /// Soluciones ordenadas de `p s² + b s t + q t² = 1` para el valor de `t` dado. Con `(p, q) =
+    /// (a, c)` se resuelve `x` en la fila `y = t`, y con `(p, q) = (c, a)` se resuelve `y` en la
+    /// columna `x = t`.
+    fn roots(&self, p: f32, q: f32, t: f32) -> Option<(f32, f32)> {
+        let linear = self.b * t;
+        let discriminant = linear * linear - 4.0 * p * (q * t * t - 1.0);
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        Some(((-linear - root) / (2.0 * p), (-linear + root) / (2.0 * p)))
+    }
+
+    /// Gradiente de `A x² + B xy + C y²` en el punto dado, perpendicular a la elipse.
+    fn gradient(&self, x: f32, y: f32) -> (f32, f32) { (2.0 * self.a * x + self.b * y, self.b * x + 2.0 * self.c * y) }
+}
+
+/// Extremo del segmento en el que degenera una elipse de semiejes conjugados paralelos, relativo al
+/// centro.
+fn degenerate_ellipse_extent(u: (f32, f32), v: (f32, f32)) -> (f32, f32) {
+    let axis = if dot(u, u) >= dot(v, v) { u } else { v };
+    match length(axis) {
+        0.0 => (0.0, 0.0),
+        norm => {
+            let direction = scale(axis, 1.0 / norm);
+            scale(direction, dot(u, direction).hypot(dot(v, direction)))
+        }
+    }
+}
+
+/// Distancia, en pixeles, del punto dado (relativo al centro) a la elipse de semiejes conjugados
+/// `u` y `v` (ver [`write_affine_ellipse`]). Es negativa dentro de la elipse.
+///
+/// Los ejes principales de la elipse se obtienen de su ecuación implícita, y el punto más cercano
+/// se busca en el cuadrante del punto refinando su dirección unas cuantas veces con el centro de
+/// curvatura de la elipse. Si la elipse degenera en un segmento, se usa la distancia al segmento.
+pub fn ellipse_distance([u, v]: [(f32, f32); 2], point: (f32, f32)) -> f32 {
+    let Some(conic) = EllipseConic::new(u, v) else {
+        let extent = degenerate_ellipse_extent(u, v);
+        let t = match dot(extent, extent) {
+            0.0 => 0.0,
+            norm => (dot(point, extent) / norm).clamp(-1.0, 1.0),
         };
+        return length(sub(point, scale(extent, t)));
+    };
 
-        buf.extend((min + 1..max).map(|x| (x, y)));
-        buf.sort_unstable();
-        buf.dedup();
+    // Valores propios de la matriz [[A, B/2], [B/2, C]]: el menor corresponde al eje mayor
+    let (mean, spread) = ((conic.a + conic.c) / 2.0, ((conic.a - conic.c) / 2.0).hypot(conic.b / 2.0));
+    let (major, minor) = (1.0 / (mean - spread).sqrt(), 1.0 / (mean + spread).sqrt());
+    let axis = match conic.b {
+        0.0 if conic.a <= conic.c => (1.0, 0.0),
+        0.0 => (0.0, 1.0),
+        b => {
+            let axis = (b / 2.0, mean - spread - conic.a);
+            scale(axis, 1.0 / length(axis))
+        }
+    };
+    let (px, py) = (dot(point, axis).abs(), cross(axis, point).abs());
+
+    let mut t = (std::f32::consts::FRAC_1_SQRT_2, std::f32::consts::FRAC_1_SQRT_2);
+    let focal = major * major - minor * minor;
+    for _ in 0..4 {
+        let evolute = (focal * t.0.powi(3) / major, -focal * t.1.powi(3) / minor);
+        let r = length(sub((major * t.0, minor * t.1), evolute));
+        let q = sub((px, py), evolute);
+        let q_length = length(q);
+        if q_length == 0.0 {
+            break;
+        }
+        let next = (
+            ((q.0 * r / q_length + evolute.0) / major).clamp(0.0, 1.0),
+            ((q.1 * r / q_length + evolute.1) / minor).clamp(0.0, 1.0),
+        );
+        t = scale(next, 1.0 / length(next));
+    }
+
+    let distance = length(sub((px, py), (major * t.0, minor * t.1)));
+    match (px / major).powi(2) + (py / minor).powi(2) < 1.0 {
+        true => -distance,
+        false => distance,
+    }
+}
+
+/// Escribe al buffer dado los puntos del contorno de un rectángulo con esquinas redondeadas, entre
+/// las esquinas `min` y `max`, usando el algoritmo de CPM para las esquinas.
+///
+/// Cada esquina es un cuarto de circunferencia de radio `r` tangente a los dos lados que une. Los
+/// pasos del primer octante se reflejan en los dos octantes de cada esquina, cada uno respecto al
+/// centro de su propia circunferencia. El radio se limita a la mitad del lado más corto.
+pub fn write_rounded_rect_middle_point(min: PixelCoord, max: PixelCoord, r: i32, puntos: &mut Vec<PixelCoord>) {
+    let ((x0, y0), (x1, y1)) = (min, max);
+    let r = r.clamp(0, (x1 - x0).min(y1 - y0) / 2);
+    // Centro de la circunferencia de cada esquina, junto con el sentido de su cuadrante
+    let corners =
+        [((x0 + r, y0 + r), (-1, -1)), ((x1 - r, y0 + r), (1, -1)), ((x1 - r, y1 - r), (1, 1)), ((x0 + r, y1 - r), (-1, 1))];
+
+    let mut plot_corners = |x: i32, y: i32| {
+        for ((cx, cy), (sx, sy)) in corners {
+            puntos.extend([(cx + sx * x, cy + sy * y), (cx + sx * y, cy + sy * x)]);
+        }
+    };
+
+    let (mut x, mut y) = (0, r);
+    let mut d = 1 - r;
+    plot_corners(x, y);
+    while x < y {
+        x += 1;
+        if d < 0 {
+            d += 2 * x + 1;
+        } else {
+            y -= 1;
+            d += 2 * (x - y) + 1;
+        }
+        plot_corners(x, y);
+    }
+
+    // Lados rectos entre las esquinas
+    for x in x0 + r..=x1 - r {
+        puntos.extend([(x, y0), (x, y1)]);
+    }
+    for y in y0 + r..=y1 - r {
+        puntos.extend([(x0, y), (x1, y)]);
+    }
+}
+
+/// Variante de [`write_circle_middle_point`] que solo escribe los puntos dentro del rectángulo de
+/// recorte.
+///
+/// Solo se recorren los pasos del octante cuyos reflejos pueden caer dentro del rectángulo. Para
+/// empezar a mitad del octante, el parámetro de decisión se calcula directamente a partir del
+/// punto, como `(x + 1)² + y² - y - r²`.
+pub fn write_circle_middle_point_clipped(centro: PixelCoord, r: i32, clip: &ClipRect, puntos: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let (min, max) = ((cx - r, cy - r), (cx + r, cy + r));
+    if !clip.intersects(min, max) {
+        return;
+    }
+    if clip.encloses(min, max) || r <= 0 {
+        return write_circle_middle_point(centro, r, puntos);
+    }
+
+    let (r2, mut x, mut y, mut d) = (r as i64 * r as i64, 0, r, 1 - r);
+    // Parámetro de decisión del punto (x, y)
+    let decision = |x: i32, y: i32| -> i64 { (x as i64 + 1).pow(2) + y as i64 * y as i64 - y as i64 - r2 };
+    // Valor de `y` en el paso `x`: el mayor con parámetro de decisión negativo en el paso anterior
+    let y_at = |x: i32| -> i32 {
+        if x == 0 {
+            return r;
+        }
+        let mut y = (r2 - x as i64 * x as i64).max(0).isqrt() as i32;
+        while decision(x - 1, y + 1) < 0 {
+            y += 1;
+        }
+        while y > 0 && decision(x - 1, y) >= 0 {
+            y -= 1;
+        }
+        y
+    };
+    let mut plot = |x: i32, y: i32| {
+        let reflections = [(x, y), (-x, y), (x, -y), (-x, -y), (y, x), (-y, x), (y, -x), (-y, -x)];
+        puntos.extend(reflections.map(|(dx, dy)| (cx + dx, cy + dy)).into_iter().filter(|&p| clip.contains(p)));
+    };
+
+    for (first, last) in octant_steps(centro, (r, r), clip, true) {
+        if first > 0 {
+            let previous = first - 1;
+            (x, y) = (previous, y_at(previous));
+            d = decision(x, y) as i32;
+            if x >= y {
+                break;
+            }
+            // Primer paso igual al de `write_circle_middle_point`
+            x += 1;
+            if d < 0 {
+                d += 2 * x + 1;
+            } else {
+                y -= 1;
+                d += 2 * (x - y) + 1;
+            }
+        }
+        plot(x, y);
+        while x < y && x < last {
+            x += 1;
+            if d < 0 {
+                d += 2 * x + 1;
+            } else {
+                y -= 1;
+                d += 2 * (x - y) + 1;
+            }
+            plot(x, y);
+        }
+    }
+}
+
+/// Variante de [`write_ellipse_middle_point`] que solo escribe los puntos dentro del rectángulo de
+/// recorte.
+///
+/// Cada región de la elipse se recorre solo en los pasos cuyos reflejos pueden caer dentro del
+/// rectángulo. Para empezar a mitad de una región, el parámetro de decisión se calcula
+/// directamente a partir del punto, con la misma constante de redondeo que el algoritmo completo.
+pub fn write_ellipse_middle_point_clipped(centro: PixelCoord, rx: i32, ry: i32, clip: &ClipRect, puntos: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let (min, max) = ((cx - rx, cy - ry), (cx + rx, cy + ry));
+    if !clip.intersects(min, max) {
+        return;
+    }
+    if clip.encloses(min, max) {
+        return write_ellipse_middle_point(centro, rx, ry, puntos);
+    }
+    if rx <= 0 || ry <= 0 {
+        // Elipse degenerada: no hay regiones que recorrer
+        let start = puntos.len();
+        write_ellipse_middle_point(centro, rx, ry, puntos);
+        let mut clipped = puntos.split_off(start);
+        clipped.retain(|&p| clip.contains(p));
+        return puntos.append(&mut clipped);
+    }
+
+    let (rx2, ry2) = (rx.pow(2), ry.pow(2));
+    let (two_rx2, two_ry2) = (2 * rx2, 2 * ry2);
+    let round = |arg: f32| -> i32 { (arg + 0.5) as i32 };
+    let mut plot = |x: i32, y: i32| {
+        let reflections = [(x, y), (-x, y), (x, -y), (-x, -y)];
+        puntos.extend(reflections.map(|(dx, dy)| (cx + dx, cy + dy)).into_iter().filter(|&p| clip.contains(p)));
+    };
+
+    // Región 1: el parámetro de decisión de (x, y) es ry²(x + 1)² + rx²(y² - y) - rx²ry² + c1
+    let p1_initial = round(ry2 as f32 - (rx2 * ry) as f32 + (0.25 * rx2 as f32));
+    let (rx2_64, ry2_64) = (rx2 as i64, ry2 as i64);
+    let f1 =
+        |x: i32, y: i32| -> i64 { ry2_64 * (x as i64 + 1).pow(2) + rx2_64 * (y as i64 * y as i64 - y as i64) - rx2_64 * ry2_64 };
+    let c1 = p1_initial as i64 - f1(0, ry);
+    let decision1 = |x: i32, y: i32| f1(x, y) + c1;
+    // Valor de `y` en el paso `x` de la región 1
+    let y_at = |x: i32| -> i32 {
+        if x == 0 {
+            return ry;
+        }
+        let ideal = ry as f64 * (1.0 - (x as f64 / rx as f64).powi(2)).max(0.0).sqrt();
+        let mut y = ideal as i32;
+        while decision1(x - 1, y + 1) < 0 {
+            y += 1;
+        }
+        while y > 0 && decision1(x - 1, y) >= 0 {
+            y -= 1;
+        }
+        y
+    };
+    // Estado (x, y, px, py, p) de la región 1 en el paso `x`
+    let state1 = |x: i32| -> (i32, i32, i32, i32, i32) {
+        let y = y_at(x);
+        (x, y, two_ry2 * x, two_rx2 * y, decision1(x, y) as i32)
+    };
+    let step1 = |(mut x, mut y, mut px, mut py, mut p): (i32, i32, i32, i32, i32)| {
+        x += 1;
+        px += two_ry2;
+        if p < 0 {
+            p += ry2 + px;
+        } else {
+            y -= 1;
+            py -= two_rx2;
+            p += ry2 + px - py;
+        }
+        (x, y, px, py, p)
+    };
+
+    // Último paso de la región 1: el primero con px >= py. Se busca con el valor ideal de `y` y
+    // se ajusta con el algoritmo a partir de dos pasos antes.
+    let (mut low, mut high) = (0, rx);
+    while low < high {
+        let mid = (low + high) / 2;
+        let ideal = ry as f64 * (1.0 - (mid as f64 / rx as f64).powi(2)).max(0.0).sqrt();
+        if ry2 as f64 * mid as f64 >= rx2 as f64 * ideal { high = mid } else { low = mid + 1 }
+    }
+    let mut end1 = if low >= 2 { step1(step1(state1(low - 2))) } else { state1(0) };
+    while end1.2 >= end1.3 && end1.0 > 0 {
+        end1 = state1(end1.0 - 1);
+    }
+    while end1.2 < end1.3 {
+        end1 = step1(end1);
+    }
+
+    for (first, last) in octant_steps(centro, (rx, ry), clip, false).into_iter().filter(|&(first, _)| first <= end1.0) {
+        let mut state = if first >= 1 { step1(state1(first - 1)) } else { state1(0) };
+        plot(state.0, state.1);
+        while state.2 < state.3 && state.0 < last {
+            state = step1(state);
+            plot(state.0, state.1);
+        }
+    }
+
+    // Región 2: el parámetro de decisión de (x, y) es ry²(x² + x) + rx²(y - 1)² - rx²ry² + c2
+    let (x_end, y_end, px_end, py_end, _) = end1;
+    let p2_initial = {
+        let (ry2, rx2, x, y) = (ry2 as f32, rx2 as f32, x_end as f32, y_end as f32);
+        round(ry2 * (x + 0.5).powi(2) + rx2 * (y - 1.0).powi(2) - rx2 * ry2)
+    };
+    let f2 =
+        |x: i32, y: i32| -> i64 { ry2_64 * (x as i64 * x as i64 + x as i64) + rx2_64 * (y as i64 - 1).pow(2) - rx2_64 * ry2_64 };
+    let c2 = p2_initial as i64 - f2(x_end, y_end);
+    let decision2 = |x: i32, y: i32| f2(x, y) + c2;
+    // Estado (x, y, px, py, p) de la región 2 en el paso `y`
+    let state2 = |y: i32| -> (i32, i32, i32, i32, i32) {
+        if y >= y_end {
+            return (x_end, y_end, px_end, py_end, p2_initial);
+        }
+        // `x` es el menor valor con parámetro de decisión positivo en el paso anterior
+        let ideal = rx as f64 * (1.0 - (y as f64 / ry as f64).powi(2)).max(0.0).sqrt();
+        let mut x = (ideal as i32).max(x_end);
+        while x > x_end && decision2(x - 1, y + 1) > 0 {
+            x -= 1;
+        }
+        while decision2(x, y + 1) <= 0 {
+            x += 1;
+        }
+        (x, y, two_ry2 * x, two_rx2 * y, decision2(x, y) as i32)
+    };
+    let step2 = |(mut x, mut y, mut px, mut py, mut p): (i32, i32, i32, i32, i32)| {
+        y -= 1;
+        py -= two_rx2;
+        if p > 0 {
+            p += rx2 - py;
+        } else {
+            x += 1;
+            px += two_ry2;
+            p += rx2 - py + px;
+        }
+        (x, y, px, py, p)
+    };
+
+    let swapped = ClipRect { x_min: clip.y_min, y_min: clip.x_min, x_max: clip.y_max, y_max: clip.x_max };
+    for (first, last) in octant_steps((cy, cx), (ry, rx), &swapped, false).into_iter().filter(|&(first, _)| first < y_end) {
+        // La región 2 avanza con `y` decreciente, desde `last` hasta `first`
+        let mut state = state2(last + 1);
+        while state.1 > first.max(0) {
+            state = step2(state);
+            plot(state.0, state.1);
+        }
+    }
+}
+
+/// Intervalos de pasos `x` (desde 0) de un arco `y = ry √(1 - x²/rx²)` cuyos reflejos respecto al
+/// centro pueden quedar dentro del rectángulo, incluyendo un margen para el redondeo de los
+/// algoritmos de punto medio.
+///
+/// Si `octants` es verdadero también se consideran los reflejos sobre la diagonal `(y, x)`, como
+/// en el círculo. Los intervalos devueltos están ordenados y no se superponen.
+fn octant_steps((cx, cy): PixelCoord, (rx, ry): (i32, i32), clip: &ClipRect, octants: bool) -> Vec<(i32, i32)> {
+    use itertools::Itertools;
+
+    // Rango de desplazamientos sobre un eje visible con el signo dado
+    let signed = |(lo, hi): (i32, i32), sign: i32| if sign > 0 { (lo, hi) } else { (-hi, -lo) };
+    // Rango de `x` en el que `y(x)` puede quedar dentro de [lo, hi]
+    let x_for_y = |(lo, hi): (i32, i32)| -> Option<(i32, i32)> {
+        let (lo, hi) = (lo - 1, hi + 1);
+        if hi < 0 || lo > ry {
+            return None;
+        }
+        let x_of = |y: i32| rx as f64 * (1.0 - (y.clamp(0, ry) as f64 / ry as f64).powi(2)).max(0.0).sqrt();
+        Some((x_of(hi).floor() as i32 - 1, x_of(lo).ceil() as i32 + 1))
+    };
+    let intersect = |(a0, a1): (i32, i32), (b0, b1): (i32, i32)| -> Option<(i32, i32)> {
+        let range = (a0.max(b0).max(0), a1.min(b1).min(rx));
+        (range.0 <= range.1).then_some(range)
+    };
+
+    let (dx, dy) = ((clip.x_min - cx, clip.x_max - cx), (clip.y_min - cy, clip.y_max - cy));
+    let mut ranges = Vec::with_capacity(8);
+    for (sx, sy) in [(1, 1), (-1, 1), (1, -1), (-1, -1)] {
+        let (along_x, along_y) = (signed(dx, sx), signed(dy, sy));
+        // Reflejo (x, y)
+        ranges.extend(x_for_y(along_y).and_then(|range| intersect(range, (along_x.0 - 1, along_x.1 + 1))));
+        // Reflejo (y, x)
+        if octants {
+            ranges.extend(x_for_y(along_x).and_then(|range| intersect(range, (along_y.0 - 1, along_y.1 + 1))));
+        }
+    }
+
+    ranges.sort_unstable();
+    ranges.into_iter().coalesce(|a, b| if b.0 <= a.1 + 1 { Ok((a.0, a.1.max(b.1))) } else { Err((a, b)) }).collect()
+}
+
+/// Escribe al buffer dado los puntos del arco de circunferencia que va de `start` a `end`, usando
+/// el algoritmo de CPM (ver [`write_circle_middle_point`]).
+///
+/// Los ángulos están en radianes y crecen en sentido antihorario en pantalla (ver
+/// [`arc_contains`]).
+pub fn write_circle_arc_middle_point(centro: PixelCoord, r: i32, start: f32, end: f32, puntos: &mut Vec<PixelCoord>) {
+    write_ellipse_arc_middle_point(centro, r, r, start, end, puntos);
+}
+
+/// Escribe al buffer dado los puntos del arco de elipse que va de `start` a `end`, usando el
+/// algoritmo de EPM (ver [`write_ellipse_middle_point`]).
+///
+/// Los puntos son los de la elipse completa cuya dirección desde el centro está dentro del arco,
+/// por lo que los ángulos son polares y no paramétricos. Solo se reflejan los octantes (o
+/// cuadrantes) que toca el arco, y solo se revisan uno por uno los puntos de los octantes que
+/// contienen sus extremos.
+pub fn write_ellipse_arc_middle_point(centro: PixelCoord, rx: i32, ry: i32, start: f32, end: f32, puntos: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let mut base = Vec::new();
+    let copies: &[(Reflection, u8)] = match rx == ry {
+        true => {
+            circle_octant(rx, |point| base.push(point));
+            &CIRCLE_OCTANTS
+        }
+        false => {
+            ellipse_quadrant(rx, ry, |point| base.push(point));
+            &ELLIPSE_QUADRANTS
+        }
+    };
+    let step = std::f32::consts::TAU / copies.len() as f32;
+    let margin = arc_margin(rx, ry);
+
+    for &(reflection, index) in copies {
+        let interval = (index as f32 * step, (index + 1) as f32 * step);
+        let points = base.iter().map(|&point| reflect(centro, point, reflection));
+        match arc_overlap(start, end, interval, margin) {
+            ArcOverlap::Full => puntos.extend(points),
+            ArcOverlap::Partial => {
+                puntos.extend(points.filter(|&(x, y)| arc_contains(start, end, ((x - cx) as f32, (y - cy) as f32))))
+            }
+            ArcOverlap::Empty => {}
+        }
+    }
+}
+
+/// Parte de un intervalo de ángulos que cubre un arco.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArcOverlap {
+    /// El intervalo está completamente fuera del arco.
+    Empty,
+    /// Uno de los extremos del arco está dentro del intervalo: hay que revisar cada punto.
+    Partial,
+    /// El intervalo está completamente dentro del arco.
+    Full,
+}
+
+/// Compara el arco de `start` a `end` con el intervalo de ángulos `(a0, a1)`, ampliado `margin`
+/// radianes hacia cada lado.
+///
+/// Si ningún extremo del arco cae dentro del intervalo, el intervalo queda completamente dentro o
+/// completamente fuera del arco, según su punto medio.
+fn arc_overlap(start: f32, end: f32, (a0, a1): (f32, f32), margin: f32) -> ArcOverlap {
+    if (end - start).abs() >= std::f32::consts::TAU {
+        return ArcOverlap::Full;
+    }
+    let direction = |angle: f32| (angle.cos(), -angle.sin());
+    let near = |angle: f32| arc_contains(a0 - margin, a1 + margin, direction(angle));
+    match near(start) || near(end) {
+        true => ArcOverlap::Partial,
+        false if arc_contains(start, end, direction((a0 + a1) / 2.0)) => ArcOverlap::Full,
+        false => ArcOverlap::Empty,
+    }
+}
+
+/// Margen angular para [`arc_overlap`] en una elipse de radios `rx` y `ry`.
+///
+/// Los octantes del CPM pueden pasarse un pixel de la diagonal, lo que cambia su ángulo en
+/// alrededor de `1 / r` radianes.
+fn arc_margin(rx: i32, ry: i32) -> f32 { 2.0 / rx.min(ry).max(1) as f32 }
+
+/// Revisa si la dirección del desplazamiento `(dx, dy)` respecto al centro está dentro del arco que
+/// va de `start` a `end`.
+///
+/// Los ángulos están en radianes y crecen en sentido antihorario en pantalla: como el eje Y de los
+/// pixeles apunta hacia abajo, el ángulo de `(dx, dy)` es `atan2(-dy, dx)`. Si `end` es menor que
+/// `start` el arco pasa por el ángulo 0, y si la diferencia es de una vuelta o más el arco es la
+/// figura completa. El centro (`(0, 0)`) pertenece a todos los arcos.
+pub fn arc_contains(start: f32, end: f32, (dx, dy): (f32, f32)) -> bool {
+    use std::f32::consts::TAU;
+
+    // Margen para conservar los pixeles justo sobre los ángulos extremos
+    const EPSILON: f32 = 1e-4;
+    if (end - start).abs() >= TAU || (dx == 0.0 && dy == 0.0) {
+        return true;
+    }
+    let sweep = (end - start).rem_euclid(TAU);
+    let angle = (f32::atan2(-dy, dx) - start).rem_euclid(TAU);
+    angle <= sweep + EPSILON || angle >= TAU - EPSILON
+}
+
+/// Desplazamiento respecto al centro del punto de la elipse en la dirección `angle`, con la misma
+/// convención de ángulos que [`arc_contains`].
+pub fn ellipse_point_at(rx: i32, ry: i32, angle: f32) -> (f32, f32) {
+    let (rx, ry) = (rx as f32, ry as f32);
+    let (sin, cos) = angle.sin_cos();
+    let radius = match (rx * sin).hypot(ry * cos) {
+        0.0 => 0.0,
+        norm => rx * ry / norm,
+    };
+    (radius * cos, -radius * sin)
+}
+
+/// Escribe al buffer dado los puntos interiores de la elipse (los mismos que [`flood_fill`] sobre
+/// [`write_ellipse_middle_point`]) que forman el sector de `start` a `end`.
+///
+/// Cada fila se recorta directamente donde la cruzan los lados del sector, por lo que el costo es
+/// lineal en el contorno de la elipse más la cantidad de puntos rellenados.
+pub fn write_ellipse_sector_fill(centro: PixelCoord, rx: i32, ry: i32, start: f32, end: f32, buf: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let inside = |(x, y): PixelCoord| arc_contains(start, end, ((x - cx) as f32, (y - cy) as f32));
+    // Cada lado del sector es un rayo desde el centro, que cruza cada fila a lo más una vez
+    let ray = |angle: f32, dy: f32| {
+        let t = -dy / angle.sin();
+        (t > 0.0 && t.is_finite()).then(|| cx as f32 + t * angle.cos())
+    };
+    write_ellipse_fill_rows(centro, rx, ry, buf, inside, |y| {
+        let dy = (y - cy) as f32;
+        // El centro pertenece a todos los sectores
+        [ray(start, dy), ray(end, dy), Some(cx as f32)].into_iter().flatten().collect()
+    });
+}
+
+/// Escribe al buffer dado los puntos interiores de la elipse (los mismos que [`flood_fill`] sobre
+/// [`write_ellipse_middle_point`]) que quedan del lado del arco de `start` a `end`, respecto a la
+/// cuerda que une sus extremos.
+///
+/// Al igual que [`write_ellipse_sector_fill`], cada fila se recorta directamente donde la cruza la
+/// cuerda.
+pub fn write_ellipse_chord_fill(centro: PixelCoord, rx: i32, ry: i32, start: f32, end: f32, buf: &mut Vec<PixelCoord>) {
+    let (cx, cy) = centro;
+    let (p0, p1) = (ellipse_point_at(rx, ry, start), ellipse_point_at(rx, ry, end));
+    let chord = (p1.0 - p0.0, p1.1 - p0.1);
+    // Los puntos del lado del arco tienen producto cruz positivo con la cuerda de inicio a fin
+    let inside = |(x, y): PixelCoord| {
+        let (dx, dy) = ((x - cx) as f32 - p0.0, (y - cy) as f32 - p0.1);
+        chord.0 * dy - chord.1 * dx >= 0.0
+    };
+    write_ellipse_fill_rows(centro, rx, ry, buf, inside, |y| {
+        let x = cx as f32 + p0.0 + chord.0 * ((y - cy) as f32 - p0.1) / chord.1;
+        x.is_finite().then_some(x).into_iter().collect()
+    });
+}
+
+/// Escribe al buffer dado los puntos interiores de la elipse que cumplen `inside`, fila por fila.
+///
+/// `cuts` devuelve las posiciones de cada fila donde `inside` puede cambiar de valor.
+fn write_ellipse_fill_rows(
+    centro: PixelCoord,
+    rx: i32,
+    ry: i32,
+    buf: &mut Vec<PixelCoord>,
+    inside: impl Fn(PixelCoord) -> bool,
+    cuts: impl Fn(i32) -> Vec<f32>,
+) {
+    let mut outline = Vec::new();
+    match rx == ry {
+        true => write_circle_middle_point(centro, rx, &mut outline),
+        false => write_ellipse_middle_point(centro, rx, ry, &mut outline),
+    }
+    let Some((top_y, spans)) = row_spans(&outline) else { return };
+    for (y, span) in (top_y..).zip(spans) {
+        let Some((min, max)) = span else { continue };
+        if max - min >= 2 {
+            write_row_between_cuts(y, (min + 1, max - 1), &cuts(y), &inside, buf);
+        }
+    }
+}
+
+/// Escribe al buffer dado los pixeles de la fila `y`, entre `x_min` y `x_max`, que cumplen
+/// `inside`.
+///
+/// `inside` solo puede cambiar de valor cerca de las posiciones `cuts`: alrededor de cada corte se
+/// revisan los pixeles uno por uno, y en cada tramo entre cortes se revisa un solo pixel.
+fn write_row_between_cuts(
+    y: i32,
+    (x_min, x_max): (i32, i32),
+    cuts: &[f32],
+    inside: impl Fn(PixelCoord) -> bool,
+    buf: &mut Vec<PixelCoord>,
+) {
+    let mut cuts =
+        cuts.iter().map(|&cut| cut.floor() as i32).filter(|&cut| cut >= x_min - 2 && cut <= x_max + 1).collect::<Vec<_>>();
+    cuts.sort_unstable();
+
+    let mut x = x_min;
+    for cut in cuts {
+        // Pixeles que se revisan uno por uno
+        let (lo, hi) = (cut - 1, (cut + 2).min(x_max));
+        if x < lo && inside((x + (lo - x) / 2, y)) {
+            buf.extend((x..lo).map(|x| (x, y)));
+        }
+        buf.extend((x.max(lo)..=hi).map(|x| (x, y)).filter(|&point| inside(point)));
+        x = x.max(hi + 1);
+    }
+    if x <= x_max && inside((x + (x_max - x) / 2, y)) {
+        buf.extend((x..=x_max).map(|x| (x, y)));
+    }
+}
+
+/// Punto de un trazo con antialiasing, junto con la cobertura del pixel (entre 0 y 1).
+pub type CoveragePoint = (PixelCoord, f32);
+
+/// Escribe al buffer dado los puntos de la línea entre `p0` y `p1` con su cobertura, usando el
+/// algoritmo de Xiaolin Wu.
+///
+/// Por cada paso en el eje mayor se escriben los dos pixeles más cercanos a la línea ideal, con una
+/// cobertura proporcional a su cercanía. Los puntos se escriben en orden desde `p0` hasta `p1`.
+pub fn write_wu_line(p0: PixelCoord, p1: PixelCoord, buf: &mut Vec<CoveragePoint>) {
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    let steep = dy.abs() > dx.abs();
+    // Se avanza sobre el eje mayor y se interpola sobre el eje menor
+    let (major, minor) = if steep { (dy, dx) } else { (dx, dy) };
+    if major == 0 {
+        buf.push((p0, 1.0));
+        return;
+    }
+
+    let gradient = minor as f32 / major as f32;
+    let step = major.signum();
+    for t in (0..=major.abs()).map(|i| i * step) {
+        let across = gradient * t as f32;
+        let base = across.floor();
+        let fraction = across - base;
+        for (offset, coverage) in [(0, 1.0 - fraction), (1, fraction)] {
+            if coverage <= 0.0 {
+                continue;
+            }
+            let across = base as i32 + offset;
+            let point = if steep { (p0.0 + across, p0.1 + t) } else { (p0.0 + t, p0.1 + across) };
+            buf.push((point, coverage));
+        }
+    }
+}
+
+/// Escribe al buffer dado los puntos de la circunferencia con su cobertura, usando el algoritmo de
+/// Xiaolin Wu.
+pub fn write_wu_circle(centro: PixelCoord, r: i32, buf: &mut Vec<CoveragePoint>) { write_wu_ellipse(centro, r, r, buf); }
+
+/// Escribe al buffer dado los puntos de la elipse con su cobertura, usando el algoritmo de Xiaolin
+/// Wu.
+///
+/// Al igual que [`write_ellipse_middle_point`], la elipse se divide en dos regiones según su
+/// pendiente: en la primera se avanza sobre el eje X, y en la segunda sobre el eje Y.
+pub fn write_wu_ellipse(centro: PixelCoord, rx: i32, ry: i32, buf: &mut Vec<CoveragePoint>) {
+    let (cx, cy) = centro;
+    if rx <= 0 || ry <= 0 {
+        // Elipse degenerada: una línea sobre el eje con radio no nulo
+        let (rx, ry) = (rx.max(0), ry.max(0));
+        write_wu_line((cx - rx, cy - ry), (cx + rx, cy + ry), buf);
+        return;
+    }
+
+    // Reflejar el cuadrante en los cuatro cuadrantes, sin repetir los pixeles sobre los ejes
+    let mut points = wu_ellipse_quadrant(rx, ry)
+        .into_iter()
+        .flat_map(|(point, coverage)| ELLIPSE_QUADRANTS.map(|(reflection, _)| (reflect(centro, point, reflection), coverage)))
+        .collect::<Vec<_>>();
+    points.sort_unstable_by_key(|&(point, _)| point);
+    points.dedup_by_key(|(point, _)| *point);
+    buf.extend(points);
+}
+
+/// Escribe al buffer dado los puntos del arco de elipse que va de `start` a `end` con su cobertura,
+/// usando el algoritmo de Xiaolin Wu (ver [`write_wu_ellipse`]).
+///
+/// Al igual que [`write_ellipse_arc_middle_point`], solo se reflejan los cuadrantes que toca el
+/// arco.
+pub fn write_wu_ellipse_arc(centro: PixelCoord, rx: i32, ry: i32, start: f32, end: f32, buf: &mut Vec<CoveragePoint>) {
+    let (cx, cy) = centro;
+    if rx <= 0 || ry <= 0 {
+        let mut line = Vec::new();
+        write_wu_ellipse(centro, rx, ry, &mut line);
+        buf.extend(line.into_iter().filter(|&((x, y), _)| arc_contains(start, end, ((x - cx) as f32, (y - cy) as f32))));
+        return;
+    }
+
+    let quadrant = wu_ellipse_quadrant(rx, ry);
+    let mut points = Vec::new();
+    for (reflection, index) in ELLIPSE_QUADRANTS {
+        let interval = (index as f32 * std::f32::consts::FRAC_PI_2, (index + 1) as f32 * std::f32::consts::FRAC_PI_2);
+        let reflected = quadrant.iter().map(|&(point, coverage)| (reflect(centro, point, reflection), coverage));
+        match arc_overlap(start, end, interval, arc_margin(rx, ry)) {
+            ArcOverlap::Full => points.extend(reflected),
+            ArcOverlap::Partial => {
+                points.extend(reflected.filter(|&((x, y), _)| arc_contains(start, end, ((x - cx) as f32, (y - cy) as f32))))
+            }
+            ArcOverlap::Empty => {}
+        }
+    }
+    // Los pixeles sobre los ejes están en dos cuadrantes
+    points.sort_unstable_by_key(|&(point, _)| point);
+    points.dedup_by_key(|(point, _)| *point);
+    buf.extend(points);
+}
+
+/// Escribe al buffer dado los puntos de la elipse de semiejes conjugados `u` y `v` con su cobertura
+/// (ver [`write_affine_ellipse`]).
+///
+/// La cobertura de cada pixel cercano al contorno se calcula a partir de su distancia a la elipse,
+/// aproximada con la ecuación implícita y su gradiente. La distancia se mide sobre el eje en el que
+/// la elipse avanza más lento, igual que en el algoritmo de Xiaolin Wu, por lo que sin girar se
+/// obtiene prácticamente lo mismo que con [`write_wu_ellipse`].
+pub fn write_wu_affine_ellipse(centro: PixelCoord, [u, v]: [(f32, f32); 2], buf: &mut Vec<CoveragePoint>) {
+    let (cx, cy) = centro;
+    let Some(conic) = EllipseConic::new(u, v) else {
+        let (dx, dy) = degenerate_ellipse_extent(u, v);
+        let (dx, dy) = (dx.round() as i32, dy.round() as i32);
+        write_wu_line((cx - dx, cy - dy), (cx + dx, cy + dy), buf);
+        return;
+    };
+
+    // Los pixeles con cobertura están a menos de un pixel del contorno sin antialiasing
+    let mut outline = Vec::new();
+    write_affine_ellipse((0, 0), [u, v], &mut outline);
+    let mut candidates = outline
+        .into_iter()
+        .flat_map(|(x, y)| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| (x + dx, y + dy))))
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    buf.extend(candidates.into_iter().filter_map(|(x, y)| {
+        let (x_f, y_f) = (x as f32, y as f32);
+        let (gx, gy) = conic.gradient(x_f, y_f);
+        // Con F = √(A x² + B xy + C y²), la distancia es (F - 1) / |∇F| y ∇F = ∇(F²) / 2F
+        let f = (conic.a * x_f * x_f + conic.b * x_f * y_f + conic.c * y_f * y_f).sqrt();
+        let distance = 2.0 * f * (f - 1.0) / gx.abs().max(gy.abs());
+        let coverage = 1.0 - distance.abs();
+        (coverage > 0.0).then_some(((cx + x, cy + y), coverage.min(1.0)))
+    }));
+}
+
+/// Puntos de un cuadrante de la elipse de radios `rx` y `ry` (positivos) centrada en el origen, con
+/// su cobertura (ver [`write_wu_ellipse`]). Cada pixel aparece una sola vez.
+fn wu_ellipse_quadrant(rx: i32, ry: i32) -> Vec<CoveragePoint> {
+    let (rx_f, ry_f) = (rx as f32, ry as f32);
+    let norm = rx_f.hypot(ry_f);
+    let mut quadrant = Vec::new();
+
+    // Región 1: pendiente menor a 1, se avanza sobre X
+    let x_limit = (rx_f * rx_f / norm).round() as i32;
+    for x in 0..=x_limit {
+        let y = ry_f * (1.0 - (x as f32 / rx_f).powi(2)).max(0.0).sqrt();
+        let base = y.floor();
+        quadrant.extend([((x, base as i32), 1.0 - (y - base)), ((x, base as i32 + 1), y - base)]);
+    }
+
+    // Región 2: pendiente mayor a 1, se avanza sobre Y
+    let y_limit = (ry_f * ry_f / norm).round() as i32;
+    for y in 0..=y_limit {
+        let x = rx_f * (1.0 - (y as f32 / ry_f).powi(2)).max(0.0).sqrt();
+        let base = x.floor();
+        quadrant.extend([((base as i32, y), 1.0 - (x - base)), ((base as i32 + 1, y), x - base)]);
+    }
+
+    // Las regiones se superponen cerca del cambio de pendiente, se conserva la mayor cobertura
+    quadrant.retain(|&(_, coverage)| coverage > 0.0);
+    quadrant.sort_unstable_by(|(a, ca), (b, cb)| a.cmp(b).then(cb.total_cmp(ca)));
+    quadrant.dedup_by_key(|(point, _)| *point);
+    quadrant
+}
+
+/// Regla utilizada para decidir si un punto se encuentra dentro de un polígono.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FillRule {
+    /// Un punto está dentro si un rayo desde él cruza el contorno un número impar de veces.
+    #[default]
+    EvenOdd,
+    /// Un punto está dentro si el contorno gira alrededor de él un número de veces distinto de
+    /// cero.
+    NonZero,
+}
+
+/// Escribe al buffer dado los puntos interiores del polígono definido por `vertices`, usando un
+/// relleno por líneas de barrido (scanline) con la regla de relleno dada.
+///
+/// El polígono se cierra implícitamente uniendo el último vértice con el primero. Funciona para
+/// polígonos cóncavos y con auto-intersecciones.
+pub fn write_polygon_scanline_fill(vertices: &[PixelCoord], rule: FillRule, buf: &mut Vec<PixelCoord>) {
+    use itertools::Itertools;
+
+    if vertices.len() < 3 {
+        return;
+    }
+    let Some((min_y, max_y)) = vertices.iter().map(|&(_, y)| y).minmax().into_option() else {
+        return;
+    };
+
+    let edges = scanline_edges(vertices);
+    let mut crossings = Vec::new();
+    for y in min_y..=max_y {
+        for (x_start, x_end) in scanline_spans(&edges, y, rule, &mut crossings) {
+            buf.extend((x_start.ceil() as i32..=x_end.floor() as i32).map(|x| (x, y)));
+        }
+    }
+}
+
+/// Revisa si el pixel dado es parte del relleno del polígono definido por `vertices`, sin generar
+/// los demás pixeles. Da el mismo resultado que buscar el pixel entre los de
+/// [`write_polygon_scanline_fill`].
+pub fn polygon_contains(vertices: &[PixelCoord], rule: FillRule, (x, y): PixelCoord) -> bool {
+    if vertices.len() < 3 {
+        return false;
+    }
+    let edges = scanline_edges(vertices);
+    let mut crossings = Vec::new();
+    scanline_spans(&edges, y, rule, &mut crossings)
+        .any(|(x_start, x_end)| x_start.ceil() as i32 <= x && x <= x_end.floor() as i32)
+}
+
+/// Bordes del polígono cerrado definido por `vertices` que cruzan alguna línea de barrido.
+fn scanline_edges(vertices: &[PixelCoord]) -> Vec<(PixelCoord, PixelCoord)> {
+    use itertools::Itertools;
+
+    // Los bordes horizontales no cruzan ninguna línea de barrido
+    vertices.iter().copied().circular_tuple_windows().filter(|(p0, p1): &(PixelCoord, PixelCoord)| p0.1 != p1.1).collect()
+}
+
+/// Tramos `(inicio, fin)` de la línea de barrido `y` que quedan dentro del polígono con los bordes
+/// dados, según la regla de relleno. `crossings` es un buffer reutilizable para los cruces.
+fn scanline_spans<'a>(
+    edges: &[(PixelCoord, PixelCoord)],
+    y: i32,
+    rule: FillRule,
+    crossings: &'a mut Vec<(f32, i32)>,
+) -> impl Iterator<Item = (f32, f32)> + 'a {
+    use itertools::Itertools;
+
+    // Cruces de la línea de barrido: (x, dirección del borde)
+    crossings.clear();
+    for &((x0, y0), (x1, y1)) in edges {
+        // Intervalo semiabierto [y_min, y_max) para no contar dos veces los vértices compartidos
+        let (winding, (top, bottom)) = if y0 < y1 { (1, ((x0, y0), (x1, y1))) } else { (-1, ((x1, y1), (x0, y0))) };
+        if y < top.1 || y >= bottom.1 {
+            continue;
+        }
+        let t = (y - top.1) as f32 / (bottom.1 - top.1) as f32;
+        crossings.push((top.0 as f32 + t * (bottom.0 - top.0) as f32, winding));
+    }
+    crossings.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+    crossings
+        .iter()
+        .tuple_windows()
+        .scan(0, move |winding, (&(x_start, w), &(x_end, _))| {
+            *winding += w;
+            let inside = match rule {
+                FillRule::EvenOdd => *winding % 2 != 0,
+                FillRule::NonZero => *winding != 0,
+            };
+            Some(inside.then_some((x_start, x_end)))
+        })
+        .flatten()
+}
+
+/// Forma de unir dos segmentos consecutivos de un trazo grueso.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum LineJoin {
+    /// Se prolongan los bordes exteriores hasta que se intersecan. Si la punta supera
+    /// [`MITER_LIMIT`] se usa [`LineJoin::Bevel`].
+    #[default]
+    Miter,
+    /// Se redondea la esquina con un disco del grosor del trazo.
+    Round,
+    /// Se corta la esquina uniendo los bordes exteriores con una recta.
+    Bevel,
+}
+
+/// Máxima relación entre el largo de la punta de una unión [`LineJoin::Miter`] y la mitad del
+/// grosor del trazo (igual al valor por defecto de SVG).
+pub const MITER_LIMIT: f32 = 4.0;
+
+/// Desplazamiento del centro de los pixeles al muestrear figuras con coordenadas reales.
+///
+/// Evita que un pixel cuyo centro queda justo sobre el borde se cuente en ambos lados, de modo que
+/// un trazo de grosor `n` cubra exactamente `n` pixeles.
+const SAMPLE_BIAS: f32 = 1.0 / 1024.0;
+
+/// Punto con coordenadas reales.
+type PointF = (f32, f32);
+
+/// Escribe al buffer dado los pixeles del trazo de grosor `width` que sigue los puntos de `path`.
+///
+/// Cada segmento se dibuja como un rectángulo de ancho `width` centrado en el segmento, y los
+/// segmentos consecutivos se unen según `join`. Si `closed` es verdadero, el último punto se une
+/// con el primero; en caso contrario los extremos terminan en corte recto.
+///
+/// # Nota
+///
+/// Los pixeles no se repiten en el buffer, aunque las partes del trazo se superpongan.
+pub fn write_thick_path(path: &[PixelCoord], closed: bool, width: f32, join: LineJoin, buf: &mut Vec<PixelCoord>) {
+    let path = path.iter().map(|&(x, y)| (x as f32, y as f32)).collect::<Vec<_>>();
+    write_thick_path_f32(&path, closed, width, join, buf);
+}
+
+/// Primer y último pixel, en un eje, del trazo de grosor `width` (ver [`write_thick_path`]) de
+/// una figura que en ese eje va de `min` a `max`. El trazo se extiende la mitad de su grosor hacia
+/// cada lado de la figura.
+pub fn thick_path_extent(min: i32, max: i32, width: f32) -> (i32, i32) {
+    let half = width / 2.0;
+    ((min as f32 - half - SAMPLE_BIAS).ceil() as i32, (max as f32 + half - SAMPLE_BIAS).floor() as i32)
+}
+
+/// Escribe al buffer dado los pixeles del contorno de grosor `width` de una elipse.
+///
+/// La elipse se aproxima con un polígono cerrado cuyos lados miden alrededor de dos pixeles, unidos
+/// con [`LineJoin::Round`].
+pub fn write_thick_ellipse(centro: PixelCoord, rx: i32, ry: i32, width: f32, buf: &mut Vec<PixelCoord>) {
+    use std::f32::consts::TAU;
+
+    let (cx, cy) = (centro.0 as f32, centro.1 as f32);
+    let (rx, ry) = (rx.max(0) as f32, ry.max(0) as f32);
+    let perimeter = TAU * ((rx * rx + ry * ry) / 2.0).sqrt();
+    let sides = ((perimeter / 2.0).ceil() as usize).max(8);
+    let path = (0..sides)
+        .map(|i| {
+            let angle = TAU * i as f32 / sides as f32;
+            (cx + rx * angle.cos(), cy + ry * angle.sin())
+        })
+        .collect::<Vec<_>>();
+    write_thick_path_f32(&path, true, width, LineJoin::Round, buf);
+}
+
+fn write_thick_path_f32(path: &[PointF], closed: bool, width: f32, join: LineJoin, buf: &mut Vec<PixelCoord>) {
+    use itertools::Itertools;
+
+    let half = width / 2.0;
+    let mut path = path.iter().copied().dedup().collect::<Vec<_>>();
+    if closed && path.len() > 1 && path.first() == path.last() {
+        path.pop();
+    }
+
+    let start = buf.len();
+    match path.len() {
+        0 => return,
+        1 => write_disk(path[0], half, buf),
+        _ => {
+            let segments = match closed {
+                true => path.iter().copied().circular_tuple_windows().collect::<Vec<_>>(),
+                false => path.iter().copied().tuple_windows().collect(),
+            };
+            for &(p0, p1) in &segments {
+                let normal = scale(perpendicular(direction(p0, p1)), half);
+                write_convex_polygon(&[add(p0, normal), add(p1, normal), sub(p1, normal), sub(p0, normal)], buf);
+            }
+
+            let joints = match closed {
+                true => segments.iter().circular_tuple_windows().collect::<Vec<_>>(),
+                false => segments.iter().tuple_windows().collect(),
+            };
+            for (&(p0, vertex), &(_, p1)) in joints {
+                write_join(p0, vertex, p1, half, join, buf);
+            }
+        }
+    }
+
+    // Las partes del trazo se superponen en las uniones
+    let mut stroke = buf.split_off(start);
+    stroke.sort_unstable();
+    stroke.dedup();
+    buf.append(&mut stroke);
+}
+
+/// Escribe al buffer dado los pixeles de la unión en `vertex` entre el segmento que viene de `p0` y
+/// el que va hacia `p1`.
+fn write_join(p0: PointF, vertex: PointF, p1: PointF, half: f32, join: LineJoin, buf: &mut Vec<PixelCoord>) {
+    let (d0, d1) = (direction(p0, vertex), direction(vertex, p1));
+    // Segmentos colineales en la misma dirección: no hay esquina que unir
+    if cross(d0, d1).abs() < 1e-6 && dot(d0, d1) > 0.0 {
+        return;
+    }
+
+    // El lado exterior de la esquina es hacia donde se separan los bordes de ambos segmentos
+    let mut outer0 = scale(perpendicular(d0), half);
+    if dot(outer0, d1) > 0.0 {
+        outer0 = scale(outer0, -1.0);
+    }
+    let mut outer1 = scale(perpendicular(d1), half);
+    if dot(outer1, d0) < 0.0 {
+        outer1 = scale(outer1, -1.0);
+    }
+    let (a, b) = (add(vertex, outer0), add(vertex, outer1));
+
+    match join {
+        LineJoin::Round => write_disk(vertex, half, buf),
+        LineJoin::Miter => {
+            // La punta está sobre la bisectriz de los bordes exteriores
+            let bisector = add(outer0, outer1);
+            let cos = dot(bisector, outer0) / (length(bisector) * half);
+            match cos > 1.0 / MITER_LIMIT {
+                true => write_convex_polygon(&[vertex, a, add(vertex, scale(bisector, half / (length(bisector) * cos))), b], buf),
+                false => write_convex_polygon(&[vertex, a, b], buf),
+            }
+        }
+        LineJoin::Bevel => write_convex_polygon(&[vertex, a, b], buf),
+    }
+}
+
+/// Escribe al buffer dado los pixeles cuyo centro está dentro del polígono convexo dado.
+fn write_convex_polygon(vertices: &[PointF], buf: &mut Vec<PixelCoord>) {
+    use itertools::Itertools;
+
+    let Some((min_y, max_y)) = vertices.iter().map(|&(_, y)| y).minmax_by(f32::total_cmp).into_option() else {
+        return;
+    };
+    for y in (min_y - SAMPLE_BIAS).ceil() as i32..=(max_y - SAMPLE_BIAS).floor() as i32 {
+        let sample_y = y as f32 + SAMPLE_BIAS;
+        let crossings = vertices.iter().circular_tuple_windows().filter_map(|(&(x0, y0), &(x1, y1))| {
+            // Intervalo semiabierto para no contar dos veces los vértices compartidos
+            if (y0 <= sample_y) == (y1 <= sample_y) {
+                return None;
+            }
+            Some(x0 + (sample_y - y0) * (x1 - x0) / (y1 - y0))
+        });
+        if let Some((left, right)) = crossings.minmax_by(f32::total_cmp).into_option() {
+            buf.extend(((left - SAMPLE_BIAS).ceil() as i32..=(right - SAMPLE_BIAS).floor() as i32).map(|x| (x, y)));
+        }
+    }
+}
+
+/// Escribe al buffer dado los pixeles cuyo centro está dentro del disco dado.
+fn write_disk((cx, cy): PointF, radius: f32, buf: &mut Vec<PixelCoord>) {
+    for y in (cy - radius - SAMPLE_BIAS).ceil() as i32..=(cy + radius - SAMPLE_BIAS).floor() as i32 {
+        let dy = y as f32 + SAMPLE_BIAS - cy;
+        let dx = (radius * radius - dy * dy).max(0.0).sqrt();
+        buf.extend(((cx - dx - SAMPLE_BIAS).ceil() as i32..=(cx + dx - SAMPLE_BIAS).floor() as i32).map(|x| (x, y)));
+    }
+}
+
+fn add(a: PointF, b: PointF) -> PointF { (a.0 + b.0, a.1 + b.1) }
+
+fn sub(a: PointF, b: PointF) -> PointF { (a.0 - b.0, a.1 - b.1) }
+
+fn scale(a: PointF, k: f32) -> PointF { (a.0 * k, a.1 * k) }
+
+fn dot(a: PointF, b: PointF) -> f32 { a.0 * b.0 + a.1 * b.1 }
+
+fn cross(a: PointF, b: PointF) -> f32 { a.0 * b.1 - a.1 * b.0 }
+
+fn length(a: PointF) -> f32 { a.0.hypot(a.1) }
+
+fn perpendicular(a: PointF) -> PointF { (-a.1, a.0) }
+
+/// Vector unitario que va de `p0` a `p1`.
+fn direction(p0: PointF, p1: PointF) -> PointF {
+    let d = sub(p1, p0);
+    scale(d, 1.0 / length(d))
+}
+
+/// Distancia máxima, en pixeles, entre una curva de Bézier y los segmentos que la aproximan.
+pub const BEZIER_TOLERANCE: f32 = 0.25;
+
+/// Límite de subdivisiones de [`flatten_bezier`], para curvas con puntos de control muy lejanos o
+/// no finitos.
+const BEZIER_MAX_DEPTH: u32 = 16;
+
+/// Aproxima la curva de Bézier con los puntos de control dados por segmentos de recta, y escribe
+/// al buffer los vértices de la polilínea resultante.
+///
+/// Con 3 puntos de control la curva es cuadrática, y con 4 es cúbica. La curva se divide a la
+/// mitad con el algoritmo de [de Casteljau][algo] mientras algún punto de control interior esté a
+/// más de `tolerance` de la cuerda entre sus extremos. Como la curva está dentro de la envolvente
+/// convexa de sus puntos de control, tampoco se aleja más que eso de la cuerda.
+///
+/// Los vértices escritos empiezan en el primer punto de control y terminan en el último.
+///
+/// [algo]: https://en.wikipedia.org/wiki/De_Casteljau%27s_algorithm
+pub fn flatten_bezier(control: &[(f32, f32)], tolerance: f32, buf: &mut Vec<(f32, f32)>) {
+    let Some(&first) = control.first() else { return };
+    buf.push(first);
+    if control.len() > 1 {
+        subdivide_bezier(control.to_vec(), tolerance, BEZIER_MAX_DEPTH, buf);
+    }
+}
+
+/// Escribe al buffer los vértices de la curva después del primero, dividiéndola hasta que cada
+/// parte sea plana.
+fn subdivide_bezier(control: Vec<PointF>, tolerance: f32, depth: u32, buf: &mut Vec<PointF>) {
+    use itertools::Itertools;
+
+    let (start, end) = (control[0], control[control.len() - 1]);
+    let chord = sub(end, start);
+    let distance = |p: PointF| {
+        match length(chord) {
+            0.0 => length(sub(p, start)),
+            chord_length => cross(chord, sub(p, start)).abs() / chord_length,
+        }
+    };
+    if depth == 0 || control[1..control.len() - 1].iter().all(|&p| distance(p) <= tolerance) {
+        buf.push(end);
+        return;
+    }
+
+    // Cada nivel de de Casteljau aporta un punto de control a cada mitad
+    let (mut left, mut right) = (Vec::with_capacity(control.len()), Vec::with_capacity(control.len()));
+    let mut level = control;
+    while let (Some(&first), Some(&last)) = (level.first(), level.last()) {
+        left.push(first);
+        right.push(last);
+        level = level.iter().tuple_windows().map(|(&a, &b)| lerp(a, b, 0.5)).collect();
+    }
+    right.reverse();
+    subdivide_bezier(left, tolerance, depth - 1, buf);
+    subdivide_bezier(right, tolerance, depth - 1, buf);
+}
+
+/// Escribe al buffer dado los puntos que forman el relleno del objeto.
+///
+/// Cada fila se rellena entre el punto más a la izquierda y el más a la derecha del contorno en
+/// dicha fila, por lo que solo es correcto para figuras convexas.
+///
+/// # Complejidad
+///
+/// Los extremos de cada fila se agrupan en una sola pasada sobre el contorno, por lo que el costo
+/// es lineal en la cantidad de puntos del contorno más la cantidad de puntos rellenados.
+pub fn flood_fill(outline_points: &[PixelCoord], buf: &mut Vec<PixelCoord>) {
+    let Some((top_y, spans)) = row_spans(outline_points) else { return };
+
+    let fill_size: usize = spans.iter().flatten().map(|&(min, max)| (max - min - 1).max(0) as usize).sum();
+    buf.reserve(fill_size);
+    for (y, span) in (top_y..).zip(spans) {
+        let Some((min, max)) = span else { continue };
+        buf.extend((min + 1..max).map(|x| (x, y)));
+    }
+}
+
+/// Extremos (mínimo, máximo) de un contorno en cada fila, o `None` si no tiene puntos en la fila.
+type RowSpans = Vec<Option<(i32, i32)>>;
+
+/// Extremos del contorno en cada fila, empezando por la fila superior, que se devuelve junto a los
+/// extremos. Devuelve `None` si el contorno está vacío.
+fn row_spans(outline_points: &[PixelCoord]) -> Option<(i32, RowSpans)> {
+    use itertools::Itertools;
+
+    let (top_y, bottom_y) = outline_points.iter().map(|&(_, y)| y).minmax().into_option()?;
+    let mut spans: RowSpans = vec![None; (bottom_y - top_y) as usize + 1];
+    for &(x, y) in outline_points {
+        let span = &mut spans[(y - top_y) as usize];
+        *span = match *span {
+            Some((min, max)) => Some((min.min(x), max.max(x))),
+            None => Some((x, x)),
+        };
+    }
+    Some((top_y, spans))
+}
+
+/// Conectividad entre pixeles vecinos utilizada por los rellenos por semilla.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Vecinos horizontales y verticales.
+    #[default]
+    Four,
+    /// Vecinos horizontales, verticales y diagonales.
+    Eight,
+}
+
+/// Escribe al buffer dado los pixeles de la región conectada a `seed` que tienen el mismo color que
+/// `seed` (herramienta de "cubeta").
+///
+/// No escribe nada si la semilla está fuera de la imagen.
+pub fn write_seed_fill(image: &RgbImage, seed: PixelCoord, connectivity: Connectivity, buf: &mut Vec<PixelCoord>) {
+    let Some(&target) = pixel_at(image, seed) else { return };
+    let is_inside = |p: PixelCoord| pixel_at(image, p) == Some(&target);
+    write_scanline_seed_fill(image.dimensions(), seed, connectivity, is_inside, buf);
+}
+
+/// Escribe al buffer dado los pixeles de la región conectada a `seed`, delimitada por los pixeles
+/// de color `boundary`.
+///
+/// No escribe nada si la semilla está fuera de la imagen o sobre el borde.
+pub fn write_boundary_fill(
+    image: &RgbImage,
+    seed: PixelCoord,
+    boundary: Color,
+    connectivity: Connectivity,
+    buf: &mut Vec<PixelCoord>,
+) {
+    let boundary = Rgb::from(boundary);
+    let is_inside = |p: PixelCoord| pixel_at(image, p).is_some_and(|pixel| *pixel != boundary);
+    write_scanline_seed_fill(image.dimensions(), seed, connectivity, is_inside, buf);
+}
+
+/// Devuelve el pixel de la imagen en la coordenada dada, si se encuentra dentro de la imagen.
+fn pixel_at(image: &RgbImage, (x, y): PixelCoord) -> Option<&Rgb<u8>> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+    image.get_pixel_checked(x as u32, y as u32)
+}
+
+/// Relleno por semilla basado en líneas de barrido.
+///
+/// En lugar de recursión, utiliza una pila de semillas: cada semilla se expande a la izquierda y a
+/// la derecha hasta encontrar un pixel fuera de la región, y se apila una nueva semilla por cada
+/// segmento de la región en las filas superior e inferior.
+fn write_scanline_seed_fill(
+    (width, height): (u32, u32),
+    seed: PixelCoord,
+    connectivity: Connectivity,
+    is_inside: impl Fn(PixelCoord) -> bool,
+    buf: &mut Vec<PixelCoord>,
+) {
+    let (width, height) = (width as i32, height as i32);
+    let mut visited = vec![false; width as usize * height as usize];
+    let index = |(x, y): PixelCoord| (y * width + x) as usize;
+    let fillable = |visited: &[bool], p: PixelCoord| {
+        (0..width).contains(&p.0) && (0..height).contains(&p.1) && !visited[index(p)] && is_inside(p)
+    };
+
+    let mut stack = vec![seed];
+    while let Some((x, y)) = stack.pop() {
+        if !fillable(&visited, (x, y)) {
+            continue;
+        }
+
+        // Expandir el segmento hacia ambos lados
+        let (mut left, mut right) = (x, x);
+        while fillable(&visited, (left - 1, y)) {
+            left -= 1;
+        }
+        while fillable(&visited, (right + 1, y)) {
+            right += 1;
+        }
+        for x in left..=right {
+            visited[index((x, y))] = true;
+            buf.push((x, y));
+        }
+
+        // Apilar una semilla por cada segmento vecino en las filas adyacentes
+        let (from, to) = match connectivity {
+            Connectivity::Four => (left, right),
+            Connectivity::Eight => (left - 1, right + 1),
+        };
+        for y in [y - 1, y + 1] {
+            let mut in_segment = false;
+            for x in from..=to {
+                match fillable(&visited, (x, y)) {
+                    true if !in_segment => {
+                        stack.push((x, y));
+                        in_segment = true;
+                    }
+                    true => {}
+                    false => in_segment = false,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::LazyLock;
+
+    use image::RgbImage;
+
+    use super::{
+        ClipRect, ClipRegion, Connectivity, CoveragePoint, FillRule, LineAlgorithm, LineJoin, arc_contains,
+        clip_line_cohen_sutherland, clip_line_liang_barsky, clip_polygon_sutherland_hodgman, ellipse_point_at, flatten_bezier,
+        flood_fill, write_affine_ellipse, write_affine_ellipse_fill, write_boundary_fill, write_bresenham,
+        write_circle_arc_middle_point, write_circle_middle_point, write_circle_middle_point_clipped, write_dda,
+        write_ellipse_arc_middle_point, write_ellipse_chord_fill, write_ellipse_middle_point, write_ellipse_middle_point_clipped,
+        write_ellipse_sector_fill, write_polygon_scanline_fill, write_rotated_ellipse, write_rounded_rect_middle_point,
+        write_seed_fill, write_thick_path, write_wu_affine_ellipse, write_wu_circle, write_wu_ellipse, write_wu_ellipse_arc,
+        write_wu_line,
+    };
+    use crate::{Color, PixelCoord};
+
+    static TRACING: LazyLock<()> = LazyLock::new(|| {
+        let _ = tracing_subscriber::fmt().without_time().with_file(true).with_line_number(true).try_init().ok();
+    });
+
+    fn bresenham(p0: PixelCoord, p1: PixelCoord) -> Vec<PixelCoord> {
+        let mut points = Vec::new();
+        write_bresenham(p0, p1, &mut points);
+        points
+    }
+
+    fn dda(p0: PixelCoord, p1: PixelCoord) -> Vec<PixelCoord> {
+        let mut points = Vec::new();
+        write_dda(p0, p1, &mut points);
+        points
+    }
+
+    #[test]
+    fn bress_360deg() {
+        *TRACING;
+        let p0 = (10, 0);
+        let p1 = (219, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_15deg() {
+        *TRACING;
+        let p0 = (10, 3);
+        let p1 = (208, 56);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_45deg() {
+        *TRACING;
+        let p0 = (10, 0);
+        let p1 = (210, 200);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_60deg() {
+        *TRACING;
+        let p0 = (0, -173);
+        let p1 = (100, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_90deg() {
+        *TRACING;
+        let p0 = (0, 0);
+        let p1 = (0, -200);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_120deg() {
+        *TRACING;
+        let p0 = (100, 0);
+        let p1 = (0, -173);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_165deg() {
+        *TRACING;
+        let p0 = (208, 3);
+        let p1 = (10, 56);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_180deg() {
+        *TRACING;
+        let p0 = (219, 555);
+        let p1 = (10, 555);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_195deg() {
+        *TRACING;
+        let p0 = (208, 56);
+        let p1 = (10, 3);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_225deg() {
+        *TRACING;
+        let p0 = (210, 200);
+        let p1 = (10, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_240deg() {
+        *TRACING;
+        let p0 = (100, 173);
+        let p1 = (0, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_270deg() {
+        *TRACING;
+        let p0 = (0, 200);
+        let p1 = (0, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_300deg() {
+        *TRACING;
+        let p0 = (0, 173);
+        let p1 = (100, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_315deg() {
+        *TRACING;
+        let p0 = (10, 200);
+        let p1 = (210, 0);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_345deg() {
+        *TRACING;
+        let p0 = (10, 56);
+        let p1 = (208, 3);
+        compare(bresenham(p0, p1), dda(p0, p1));
+    }
+
+    #[test]
+    fn bress_single_point() {
+        assert_eq!(bresenham((5, 5), (5, 5)), vec![(5, 5)]);
+    }
+
+    #[test]
+    fn bress_writes_from_p0_to_p1() {
+        for (p0, p1) in [((0, 0), (30, 7)), ((30, 7), (0, 0)), ((3, 40), (-2, 0)), ((-2, 0), (3, 40))] {
+            let points = bresenham(p0, p1);
+            assert_eq!(points.first(), Some(&p0));
+            assert_eq!(points.last(), Some(&p1));
+        }
+    }
+
+    #[test]
+    fn bress_is_symmetric() {
+        for (p0, p1) in [((0, 0), (30, 7)), ((3, 40), (-2, 0)), ((0, 0), (10, 10)), ((-7, 3), (13, -4))] {
+            let mut forward = bresenham(p0, p1);
+            let mut backward = bresenham(p1, p0);
+            forward.sort();
+            backward.sort();
+            assert_eq!(forward, backward);
+        }
+    }
+
+    /// Pentagrama con auto-intersecciones, cuyo centro es un "hueco" según la regla par-impar.
+    const PENTAGRAM: [PixelCoord; 5] = [(50, 0), (79, 90), (2, 35), (98, 35), (21, 90)];
+
+    #[test]
+    fn scanline_fill_concave_polygon() {
+        // Figura en forma de "U": la muesca central no debe rellenarse
+        let u_shape = [(0, 0), (10, 0), (10, 20), (20, 20), (20, 0), (30, 0), (30, 30), (0, 30)];
+        let mut fill = Vec::new();
+        write_polygon_scanline_fill(&u_shape, FillRule::EvenOdd, &mut fill);
+
+        assert!(fill.contains(&(5, 10)), "Brazo izquierdo");
+        assert!(fill.contains(&(25, 10)), "Brazo derecho");
+        assert!(fill.contains(&(15, 25)), "Base");
+        assert!(!fill.contains(&(15, 10)), "Muesca");
+    }
+
+    #[test]
+    fn scanline_fill_rules() {
+        let (mut even_odd, mut non_zero) = (Vec::new(), Vec::new());
+        write_polygon_scanline_fill(&PENTAGRAM, FillRule::EvenOdd, &mut even_odd);
+        write_polygon_scanline_fill(&PENTAGRAM, FillRule::NonZero, &mut non_zero);
+
+        assert!(!even_odd.contains(&(50, 50)), "El centro es un hueco con la regla par-impar");
+        assert!(non_zero.contains(&(50, 50)), "El centro se rellena con la regla distinta de cero");
+        assert!(even_odd.contains(&(50, 20)) && non_zero.contains(&(50, 20)), "Las puntas se rellenan con ambas reglas");
+    }
+
+    #[test]
+    fn scanline_fill_degenerate_polygon() {
+        let mut fill = Vec::new();
+        write_polygon_scanline_fill(&[(0, 0), (10, 10)], FillRule::EvenOdd, &mut fill);
+        write_polygon_scanline_fill(&[(0, 0), (10, 0), (20, 0)], FillRule::EvenOdd, &mut fill);
+        assert!(fill.is_empty());
+    }
+
+    #[test]
+    fn flood_fill_spans_between_row_extremes() {
+        let outline = [(0, 0), (4, 0), (2, 1), (-3, 2), (3, 2), (1, 2), (7, 4), (9, 4)];
+        let mut fill = Vec::new();
+        flood_fill(&outline, &mut fill);
+        fill.sort();
+
+        let expected = [(-2, 2), (-1, 2), (0, 2), (1, 2), (1, 0), (2, 0), (2, 2), (3, 0), (8, 4)];
+        let mut expected = expected.to_vec();
+        expected.sort();
+        assert_eq!(fill, expected);
+    }
+
+    /// Lienzo blanco de 10x10 con un marco negro de 1 pixel entre (2, 2) y (7, 7), y una
+    /// diagonal negra que separa la esquina superior izquierda del marco.
+    fn framed_canvas() -> RgbImage {
+        let mut image = RgbImage::from_pixel(10, 10, Color::from_u32_rgb(0xFFFFFF).into());
+        for k in 2..=7 {
+            for (x, y) in [(k, 2), (k, 7), (2, k), (7, k)] {
+                image.put_pixel(x, y, Color::BLACK.into());
+            }
+        }
+        for k in 0..=1 {
+            image.put_pixel(k, 1 - k, Color::BLACK.into());
+        }
+        image
+    }
+
+    #[test]
+    fn seed_fill_inside_frame() {
+        let image = framed_canvas();
+        let mut fill = Vec::new();
+        write_seed_fill(&image, (4, 4), Connectivity::Four, &mut fill);
+        fill.sort();
+
+        let mut expected = (3..=6).flat_map(|y| (3..=6).map(move |x| (x, y))).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(fill, expected);
+    }
+
+    #[test]
+    fn seed_fill_connectivity() {
+        let image = framed_canvas();
+        let (mut four, mut eight) = (Vec::new(), Vec::new());
+        write_seed_fill(&image, (9, 9), Connectivity::Four, &mut four);
+        write_seed_fill(&image, (9, 9), Connectivity::Eight, &mut eight);
+
+        // La diagonal solo separa la esquina (0, 0) con conectividad 4
+        assert!(!four.contains(&(0, 0)));
+        assert!(eight.contains(&(0, 0)));
+        assert!(!eight.contains(&(4, 4)), "El interior del marco está aislado");
+        assert_eq!(eight.len(), four.len() + 1);
+    }
+
+    #[test]
+    fn boundary_fill_stops_at_boundary_color() {
+        let mut image = framed_canvas();
+        // Pixel de otro color dentro del marco: se rellena con la regla de borde, pero no con la de
+        // semilla.
+        image.put_pixel(5, 5, Color::from_u32_rgb(0xFF0000).into());
+        let (mut boundary, mut seed) = (Vec::new(), Vec::new());
+        write_boundary_fill(&image, (4, 4), Color::BLACK, Connectivity::Four, &mut boundary);
+        write_seed_fill(&image, (4, 4), Connectivity::Four, &mut seed);
+
+        assert_eq!(boundary.len(), 16);
+        assert!(boundary.contains(&(5, 5)));
+        assert_eq!(seed.len(), 15);
+    }
+
+    #[test]
+    fn seed_fill_outside_image() {
+        let image = framed_canvas();
+        let mut fill = Vec::new();
+        write_seed_fill(&image, (-1, 4), Connectivity::Four, &mut fill);
+        write_boundary_fill(&image, (4, 40), Color::BLACK, Connectivity::Four, &mut fill);
+        assert!(fill.is_empty());
+    }
+
+    #[test]
+    fn wu_line_coverage_sums_to_one_per_step() {
+        for end in [(20, 7), (7, 20), (-20, 7), (-7, -20), (20, 0), (0, -20)] {
+            let mut points = Vec::new();
+            write_wu_line((0, 0), end, &mut points);
+            assert_eq!(points.first(), Some(&((0, 0), 1.0)), "La línea debe iniciar en p0");
+            assert_eq!(points.last(), Some(&(end, 1.0)), "La línea debe terminar en p1");
+
+            let steep = end.1.abs() > end.0.abs();
+            let mut steps = std::collections::BTreeMap::<i32, f32>::new();
+            for ((x, y), coverage) in points {
+                *steps.entry(if steep { y } else { x }).or_default() += coverage;
+            }
+            assert_eq!(steps.len() as i32, end.0.abs().max(end.1.abs()) + 1);
+            assert!(steps.values().all(|&sum| (sum - 1.0).abs() < 1e-5), "{end:?}: {steps:?}");
+        }
+    }
+
+    #[test]
+    fn wu_circle_follows_radius() {
+        let (center, radius) = ((50, 50), 20);
+        let mut points = Vec::new();
+        write_wu_circle(center, radius, &mut points);
+
+        let mut unique = points.iter().map(|&(p, _)| p).collect::<Vec<_>>();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), points.len(), "No deben repetirse pixeles");
+
+        for ((x, y), coverage) in points {
+            assert!(coverage > 0.0 && coverage <= 1.0);
+            let distance = ((x - center.0) as f32).hypot((y - center.1) as f32);
+            assert!((distance - radius as f32).abs() < 1.5, "({x}, {y}) está a {distance} del centro");
+        }
+    }
+
+    #[test]
+    fn wu_ellipse_degenerate() {
+        let mut points = Vec::new();
+        write_wu_ellipse((10, 10), 0, 3, &mut points);
+        assert_eq!(points, (7..=13).map(|y| ((10, y), 1.0)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn wu_affine_ellipse() {
+        use std::f32::consts::PI;
+
+        // Sin girar, una circunferencia da casi los mismos pixeles y coberturas que la de ejes
+        // alineados, salvo por la aproximación de la distancia
+        let coverage_at = |points: &[CoveragePoint], point| points.iter().find(|&&(p, _)| p == point).map_or(0.0, |&(_, c)| c);
+        for r in [7, 10, 25] {
+            let mut expected = Vec::new();
+            write_wu_circle((3, -4), r, &mut expected);
+            let mut points = Vec::new();
+            write_wu_affine_ellipse((3, -4), [(r as f32, 0.0), (0.0, r as f32)], &mut points);
+            for &(point, _) in points.iter().chain(&expected) {
+                let (coverage, expected) = (coverage_at(&points, point), coverage_at(&expected, point));
+                assert!((coverage - expected).abs() < 0.1, "circunferencia de radio {r}: {point:?} {coverage} != {expected}");
+            }
+        }
+
+        for (rx, ry, angle) in [(30.0, 12.0, 0.0), (30.0, 12.0, PI / 6.0), (8.0, 25.0, 1.0), (40.0, 3.0, -0.7)] {
+            let (sin, cos) = f32::sin_cos(angle);
+            let axes = [(rx * cos, -rx * sin), (ry * sin, ry * cos)];
+            let distance = |(x, y): PixelCoord| {
+                let (u, v) = (x as f32 * cos - y as f32 * sin, x as f32 * sin + y as f32 * cos);
+                let q = ((u / rx).powi(2) + (v / ry).powi(2)).sqrt();
+                (q - 1.0).abs() * rx.min(ry)
+            };
+            let mut outline = Vec::new();
+            write_affine_ellipse((0, 0), axes, &mut outline);
+            let mut points = Vec::new();
+            write_wu_affine_ellipse((0, 0), axes, &mut points);
+
+            for &(point, coverage) in &points {
+                assert!(coverage > 0.0 && coverage <= 1.0, "elipse {rx}x{ry} a {angle}: cobertura {coverage}");
+                assert!(distance(point) < 1.5, "elipse {rx}x{ry} a {angle}: {point:?} está lejos del contorno");
+            }
+            // El contorno sin antialiasing queda cubierto, así que no hay huecos
+            for point in outline {
+                assert!(coverage_at(&points, point) > 0.0, "elipse {rx}x{ry} a {angle}: falta {point:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn thick_path_covers_width() {
+        for width in 1..=6 {
+            let mut stroke = Vec::new();
+            write_thick_path(&[(0, 10), (20, 10)], false, width as f32, LineJoin::Miter, &mut stroke);
+            let rows = stroke.iter().filter(|&&(x, _)| x == 5).count();
+            assert_eq!(rows, width, "Un trazo horizontal de grosor {width} debe cubrir {width} filas");
+            assert!(stroke.iter().all(|&(x, _)| (0..=20).contains(&x)), "Los extremos terminan en corte recto");
+        }
+    }
+
+    #[test]
+    fn thick_path_joins() {
+        // Esquina en ángulo recto: la unión de inglete cubre la esquina exterior completa
+        let path = [(0, 0), (20, 0), (20, 20)];
+        let corner = |join| {
+            let mut stroke = Vec::new();
+            write_thick_path(&path, false, 9.0, join, &mut stroke);
+            let mut unique = stroke.clone();
+            unique.dedup();
+            assert_eq!(unique.len(), stroke.len(), "{join:?}: No deben repetirse pixeles");
+            stroke
+        };
+        let (miter, round, bevel) = (corner(LineJoin::Miter), corner(LineJoin::Round), corner(LineJoin::Bevel));
+        assert!(miter.contains(&(24, -4)));
+        assert!(!round.contains(&(24, -4)) && round.contains(&(23, -2)));
+        assert!(!bevel.contains(&(23, -2)) && bevel.contains(&(22, -2)));
+        assert!(bevel.len() < round.len() && round.len() < miter.len());
+    }
+
+    #[test]
+    fn thick_path_miter_limit() {
+        // Ángulo muy agudo: la punta del inglete supera el límite y se usa un bisel
+        let path = [(0, 0), (40, 2), (0, 4)];
+        let (mut miter, mut bevel) = (Vec::new(), Vec::new());
+        write_thick_path(&path, false, 4.0, LineJoin::Miter, &mut miter);
+        write_thick_path(&path, false, 4.0, LineJoin::Bevel, &mut bevel);
+        assert_eq!(miter, bevel);
+    }
+
+    /// Generador pseudoaleatorio simple (LCG) para las pruebas de recorte.
+    fn lcg(seed: u64) -> impl FnMut(i32, i32) -> i32 {
+        let mut state = seed;
+        move |lo, hi| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            lo + ((state >> 33) % (hi - lo + 1) as u64) as i32
+        }
+    }
+
+    const CLIP: ClipRect = ClipRect { x_min: 10, y_min: 20, x_max: 89, y_max: 69 };
+
+    #[test]
+    fn clip_line_algorithms_agree() {
+        let mut random = lcg(7);
+        for _ in 0..2000 {
+            let p0 = (random(-50, 150) as f32, random(-50, 150) as f32);
+            let p1 = (random(-50, 150) as f32, random(-50, 150) as f32);
+            let (cohen, liang) = (clip_line_cohen_sutherland(p0, p1, &CLIP), clip_line_liang_barsky(p0, p1, &CLIP));
+            match (cohen, liang) {
+                (None, None) => {}
+                (Some((a0, a1)), Some((b0, b1))) => {
+                    let close = |a: (f32, f32), b: (f32, f32)| (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3;
+                    assert!(close(a0, b0) && close(a1, b1), "{p0:?} -> {p1:?}: {cohen:?} != {liang:?}");
+                }
+                // Segmentos que solo tocan una esquina pueden diferir por redondeo
+                (Some((a0, a1)), None) | (None, Some((a0, a1))) => {
+                    assert!((a0.0 - a1.0).abs() < 1e-3 && (a0.1 - a1.1).abs() < 1e-3, "{p0:?} -> {p1:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clipped_lines_match_filtered() {
+        let mut random = lcg(11);
+        // Líneas de longitud cero dentro del rectángulo, en el margen de un pixel y lejos de él
+        let degenerate =
+            [(40, 40), (CLIP.x_min - 1, 40), (40, CLIP.y_max + 1), (CLIP.x_max + 1, CLIP.y_min - 1), (-150, 40)].map(|p| (p, p));
+        for algorithm in [LineAlgorithm::Dda, LineAlgorithm::Bresenham] {
+            let random_lines =
+                std::iter::repeat_with(|| ((random(-200, 300), random(-200, 300)), (random(-200, 300), random(-200, 300))));
+            for (p0, p1) in degenerate.into_iter().chain(random_lines.take(2000)) {
+                let mut expected = Vec::new();
+                algorithm.write_line(p0, p1, &mut expected);
+                expected.retain(|&p| CLIP.contains(p));
+
+                let mut clipped = Vec::new();
+                algorithm.write_line_clipped(p0, p1, &CLIP, &mut clipped);
+                assert_eq!(clipped, expected, "{algorithm:?}: {p0:?} -> {p1:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn clipped_circles_match_filtered() {
+        let mut random = lcg(13);
+        for _ in 0..1500 {
+            let (center, r) = ((random(-100, 200), random(-100, 200)), random(0, 150));
+            let mut expected = Vec::new();
+            write_circle_middle_point(center, r, &mut expected);
+            expected.retain(|&p| CLIP.contains(p));
+            expected.sort();
+
+            let mut clipped = Vec::new();
+            write_circle_middle_point_clipped(center, r, &CLIP, &mut clipped);
+            clipped.sort();
+            assert_eq!(clipped, expected, "centro {center:?}, radio {r}");
+        }
+    }
+
+    #[test]
+    fn clipped_ellipses_match_filtered() {
+        let mut random = lcg(17);
+        for _ in 0..1500 {
+            let (center, rx, ry) = ((random(-100, 200), random(-100, 200)), random(0, 150), random(0, 150));
+            let mut expected = Vec::new();
+            write_ellipse_middle_point(center, rx, ry, &mut expected);
+            expected.retain(|&p| CLIP.contains(p));
+            expected.sort();
+
+            let mut clipped = Vec::new();
+            write_ellipse_middle_point_clipped(center, rx, ry, &CLIP, &mut clipped);
+            clipped.sort();
+            assert_eq!(clipped, expected, "centro {center:?}, radios ({rx}, {ry})");
+        }
+    }
+
+    #[test]
+    fn polygon_clipping() {
+        let square = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let triangle = [(5.0, -5.0), (15.0, 5.0), (5.0, 15.0)];
+        let mut clipped = clip_polygon_sutherland_hodgman(&triangle, &square);
+        assert_eq!(clipped, vec![(5.0, 10.0), (5.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        // El sentido de giro de la región de recorte no cambia el resultado
+        let reversed = square.iter().rev().copied().collect::<Vec<_>>();
+        let mut clipped_reversed = clip_polygon_sutherland_hodgman(&triangle, &reversed);
+        clipped.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        clipped_reversed.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(clipped, clipped_reversed);
+
+        // Los polígonos dentro de la región se conservan, aunque empiecen en otro vértice
+        let inside = [(2.0, 2.0), (8.0, 2.0), (5.0, 8.0)];
+        assert_eq!(clip_polygon_sutherland_hodgman(&inside, &square), [(8.0, 2.0), (5.0, 8.0), (2.0, 2.0)]);
+        let outside = [(20.0, 20.0), (30.0, 20.0), (25.0, 30.0)];
+        assert!(clip_polygon_sutherland_hodgman(&outside, &square).is_empty());
+    }
+
+    #[test]
+    fn clip_regions() {
+        assert!(ClipRegion::new(vec![(0, 0), (10, 0)]).is_none());
+        assert!(ClipRegion::new(vec![(0, 0), (5, 5), (10, 10)]).is_none());
+        assert!(ClipRegion::new(vec![(0, 0), (10, 0), (5, 2), (10, 10), (0, 10)]).is_none());
+        // Estrella de cinco puntas: siempre gira hacia el mismo lado, pero se cruza a sí misma
+        assert!(ClipRegion::new(vec![(0, -10), (6, 8), (-9, -3), (9, -3), (-6, 8)]).is_none());
+        // Un cuadrado que termina en su primer vértice sigue siendo convexo
+        let square = ClipRegion::new(vec![(0, 0), (10, 0), (10, 10), (10, 10), (0, 10), (0, 0)]).unwrap();
+        assert_eq!(square.vertices(), [(0, 0), (10, 0), (10, 10), (0, 10)]);
+
+        let triangle = ClipRegion::new(vec![(0, 0), (0, 10), (10, 0)]).unwrap();
+        assert!(triangle.contains((0, 0)) && triangle.contains((5, 5)) && triangle.contains((2, 3)));
+        assert!(!triangle.contains((6, 5)) && !triangle.contains((-1, 0)));
+        assert_eq!(triangle.visible_bounds(&ClipRect::new((2, -5), (20, 6))), Some(ClipRect::new((2, 0), (10, 6))));
+        assert_eq!(triangle.visible_bounds(&ClipRect::new((8, 8), (20, 20))), None);
+
+        let rect = ClipRegion::from(CLIP);
+        let mut random = lcg(23);
+        for _ in 0..500 {
+            let p = (random(0, 100), random(0, 100));
+            assert_eq!(rect.contains(p), CLIP.contains(p), "punto {p:?}");
+        }
+    }
+
+    #[test]
+    fn bezier_flattening() {
+        let point_at = |control: &[(f32, f32)], t: f32| {
+            let mut level = control.to_vec();
+            while level.len() > 1 {
+                level = level.windows(2).map(|w| (w[0].0 + t * (w[1].0 - w[0].0), w[0].1 + t * (w[1].1 - w[0].1))).collect();
+            }
+            level[0]
+        };
+        let distance_to_segment = |p: (f32, f32), a: (f32, f32), b: (f32, f32)| {
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / (dx * dx + dy * dy).max(f32::EPSILON)).clamp(0.0, 1.0);
+            (p.0 - a.0 - t * dx).hypot(p.1 - a.1 - t * dy)
+        };
+
+        // Una curva con los puntos de control alineados es un solo segmento
+        let mut straight = Vec::new();
+        flatten_bezier(&[(0.0, 0.0), (5.0, 5.0), (10.0, 10.0)], 0.25, &mut straight);
+        assert_eq!(straight, vec![(0.0, 0.0), (10.0, 10.0)]);
+
+        let curves: [&[(f32, f32)]; 3] = [
+            &[(0.0, 0.0), (50.0, 100.0), (100.0, 0.0)],
+            &[(0.0, 0.0), (0.0, 100.0), (100.0, -100.0), (100.0, 0.0)],
+            &[(10.0, 10.0), (90.0, 90.0), (10.0, 90.0), (90.0, 10.0)],
+        ];
+        for control in curves {
+            for tolerance in [0.25, 1.0, 4.0] {
+                let mut vertices = Vec::new();
+                flatten_bezier(control, tolerance, &mut vertices);
+                assert_eq!(vertices.first(), control.first());
+                assert_eq!(vertices.last(), control.last());
+
+                for i in 0..=200 {
+                    let p = point_at(control, i as f32 / 200.0);
+                    let d = vertices.windows(2).map(|w| distance_to_segment(p, w[0], w[1])).fold(f32::INFINITY, f32::min);
+                    assert!(d <= tolerance + 1e-3, "{control:?}: punto {p:?} a {d} de la polilínea");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn arcs() {
+        use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+        let sorted = |mut points: Vec<PixelCoord>| {
+            points.sort();
+            points.dedup();
+            points
+        };
+        for (rx, ry) in [(20, 20), (30, 12), (7, 25)] {
+            let mut full = Vec::new();
+            match rx == ry {
+                true => write_circle_middle_point((0, 0), rx, &mut full),
+                false => write_ellipse_middle_point((0, 0), rx, ry, &mut full),
+            }
+            let mut arc = Vec::new();
+            write_ellipse_arc_middle_point((0, 0), rx, ry, 1.0, 1.0 + TAU, &mut arc);
+            assert_eq!(sorted(arc), sorted(full.clone()));
+
+            // Dos arcos complementarios cubren la elipse completa
+            let (mut first, mut second) = (Vec::new(), Vec::new());
+            write_ellipse_arc_middle_point((0, 0), rx, ry, 0.5, 2.5, &mut first);
+            write_ellipse_arc_middle_point((0, 0), rx, ry, 2.5, 0.5, &mut second);
+            first.append(&mut second);
+            assert_eq!(sorted(first), sorted(full));
+        }
+
+        // El primer cuadrante está arriba a la derecha en pantalla
+        let mut quarter = Vec::new();
+        write_circle_arc_middle_point((50, 50), 10, 0.0, FRAC_PI_2, &mut quarter);
+        assert!(quarter.contains(&(60, 50)) && quarter.contains(&(50, 40)));
+        assert!(quarter.iter().all(|&(x, y)| x >= 50 && y <= 50));
+
+        assert!(arc_contains(PI, -PI / 2.0, (0.0, 10.0)));
+        assert!(!arc_contains(PI, -PI / 2.0, (10.0, 0.0)));
+        assert!(arc_contains(0.3, 0.4, (0.0, 0.0)));
+
+        let (x, y) = ellipse_point_at(30, 10, FRAC_PI_2);
+        assert!(x.abs() < 1e-4 && (y + 10.0).abs() < 1e-4);
+        let (x, y) = ellipse_point_at(30, 10, PI / 4.0);
+        assert!((x + y).abs() < 1e-4 && (x / 30.0).powi(2) + (y / 10.0).powi(2) - 1.0 < 1e-4);
+    }
+
+    /// Los arcos, sectores y segmentos generan los mismos puntos que filtrar la elipse completa.
+    #[test]
+    fn arcs_match_filtered_ellipse() {
+        let sorted = |mut points: Vec<PixelCoord>| {
+            points.sort();
+            points.dedup();
+            points
+        };
+        let mut random = lcg(14);
+        for _ in 0..300 {
+            let (rx, ry) = (random(0, 40), random(0, 40));
+            let (rx, ry) = if random(0, 3) == 0 { (rx, rx) } else { (rx, ry) };
+            let (start, end) = (random(-700, 700) as f32 / 100.0, random(-700, 700) as f32 / 100.0);
+            let center = (random(-5, 5), random(-5, 5));
+            let in_arc = |(x, y): PixelCoord| arc_contains(start, end, ((x - center.0) as f32, (y - center.1) as f32));
+            let case = format!("radios ({rx}, {ry}), ángulos {start}..{end}");
+
+            let mut full = Vec::new();
+            match rx == ry {
+                true => write_circle_middle_point(center, rx, &mut full),
+                false => write_ellipse_middle_point(center, rx, ry, &mut full),
+            }
+            let mut arc = Vec::new();
+            write_ellipse_arc_middle_point(center, rx, ry, start, end, &mut arc);
+            let expected = full.iter().copied().filter(|&point| in_arc(point)).collect();
+            assert_eq!(sorted(arc), sorted(expected), "{case}");
+
+            let mut fill = Vec::new();
+            flood_fill(&full, &mut fill);
+            let mut sector = Vec::new();
+            write_ellipse_sector_fill(center, rx, ry, start, end, &mut sector);
+            let expected = fill.iter().copied().filter(|&point| in_arc(point)).collect();
+            assert_eq!(sorted(sector), sorted(expected), "sector, {case}");
+
+            let (p0, p1) = (ellipse_point_at(rx, ry, start), ellipse_point_at(rx, ry, end));
+            let mut segment = Vec::new();
+            write_ellipse_chord_fill(center, rx, ry, start, end, &mut segment);
+            let expected = fill
+                .iter()
+                .copied()
+                .filter(|&(x, y)| {
+                    let (dx, dy) = ((x - center.0) as f32 - p0.0, (y - center.1) as f32 - p0.1);
+                    (p1.0 - p0.0) * dy - (p1.1 - p0.1) * dx >= 0.0
+                })
+                .collect();
+            assert_eq!(sorted(segment), sorted(expected), "segmento, {case}");
+
+            let mut wu = Vec::new();
+            write_wu_ellipse(center, rx, ry, &mut wu);
+            wu.retain(|&(point, _)| in_arc(point));
+            let mut wu_arc = Vec::new();
+            write_wu_ellipse_arc(center, rx, ry, start, end, &mut wu_arc);
+            assert_eq!(wu_arc, wu, "antialiasing, {case}");
+        }
+    }
+
+    #[test]
+    fn rounded_rect() {
+        let sorted = |mut points: Vec<PixelCoord>| {
+            points.sort();
+            points.dedup();
+            points
+        };
+
+        // Sin radio, el contorno es el de un rectángulo
+        let mut square = Vec::new();
+        write_rounded_rect_middle_point((0, 0), (10, 6), 0, &mut square);
+        let mut expected =
+            (0..=10).flat_map(|x| [(x, 0), (x, 6)]).chain((0..=6).flat_map(|y| [(0, y), (10, y)])).collect::<Vec<_>>();
+        assert_eq!(sorted(square), sorted(expected.clone()));
+
+        // Con el radio máximo, un cuadrado es un círculo
+        let mut circle = Vec::new();
+        write_rounded_rect_middle_point((-12, -12), (12, 12), 50, &mut circle);
+        expected.clear();
+        write_circle_middle_point((0, 0), 12, &mut expected);
+        assert_eq!(sorted(circle), sorted(expected));
+
+        // Cada esquina es un cuarto de circunferencia alrededor de su propio centro
+        let mut rounded = Vec::new();
+        write_rounded_rect_middle_point((0, 0), (40, 20), 6, &mut rounded);
+        let mut corner = Vec::new();
+        write_circle_middle_point((34, 14), 6, &mut corner);
+        corner.retain(|&(x, y)| x >= 34 && y >= 14);
+        assert!(corner.iter().all(|p| rounded.contains(p)));
+        assert!(!rounded.contains(&(40, 20)) && rounded.contains(&(40, 14)) && rounded.contains(&(34, 20)));
+    }
+
+    #[test]
+    fn ellipse_distances() {
+        use std::f32::consts::TAU;
+
+        use itertools::Itertools;
+
+        // Con ejes conjugados no perpendiculares, la distancia debe coincidir con la mínima a los
+        // puntos de la elipse
+        for axes in
+            [[(10.0, 0.0), (0.0, 10.0)], [(30.0, 0.0), (0.0, 12.0)], [(26.0, -15.0), (6.0, 10.4)], [(30.0, 0.0), (10.0, 12.0)]]
+        {
+            let [u, v] = axes;
+            let samples = (0..4000)
+                .map(|i| (i as f32 / 4000.0 * TAU).sin_cos())
+                .map(|(sin, cos)| (u.0 * cos + v.0 * sin, u.1 * cos + v.1 * sin))
+                .collect::<Vec<_>>();
+            for (x, y) in (-45..=45).step_by(5).cartesian_product((-45..=45).step_by(5)) {
+                let (x, y) = (x as f32, y as f32);
+                let expected = samples.iter().map(|&(sx, sy)| (sx - x).hypot(sy - y)).fold(f32::INFINITY, f32::min);
+                let distance = super::ellipse_distance(axes, (x, y));
+                assert!((distance.abs() - expected).abs() < 0.05, "elipse {axes:?}: {distance} != {expected} en {:?}", (x, y));
+            }
+        }
+
+        // Dentro de la elipse la distancia es negativa, y una elipse degenerada mide hasta el segmento
+        assert_eq!(super::ellipse_distance([(10.0, 0.0), (0.0, 10.0)], (0.0, 0.0)), -10.0);
+        assert!(super::ellipse_distance([(30.0, 0.0), (0.0, 12.0)], (20.0, 5.0)) < 0.0);
+        assert_eq!(super::ellipse_distance([(3.0, 4.0), (6.0, 8.0)], (0.0, 0.0)), 0.0);
+        assert_eq!(super::ellipse_distance([(3.0, 0.0), (6.0, 0.0)], (0.0, 2.0)), 2.0);
+    }
+
+    #[test]
+    fn affine_ellipses() {
+        use std::f32::consts::PI;
+
+        let neighbors = |points: &[PixelCoord], (x, y): PixelCoord| {
+            points.iter().filter(|&&(px, py)| (px, py) != (x, y) && (px - x).abs() <= 1 && (py - y).abs() <= 1).count()
+        };
+        for (rx, ry, angle) in [(30.0, 12.0, 0.0), (30.0, 12.0, PI / 6.0), (8.0, 25.0, 1.0), (20.0, 20.0, 0.4), (40.0, 3.0, -0.7)]
+        {
+            let (sin, cos) = f32::sin_cos(angle);
+            // Distancia aproximada del punto a la elipse, en pixeles
+            let distance = |(x, y): PixelCoord| {
+                let (u, v) = (x as f32 * cos - y as f32 * sin, x as f32 * sin + y as f32 * cos);
+                let q = ((u / rx).powi(2) + (v / ry).powi(2)).sqrt();
+                (q - 1.0).abs() * rx.min(ry)
+            };
+
+            let mut outline = Vec::new();
+            write_rotated_ellipse((0, 0), rx, ry, angle, &mut outline);
+            for &point in &outline {
+                assert!(distance(point) <= 1.0, "elipse {rx}x{ry} a {angle}: {point:?} está lejos del contorno");
+                assert!(neighbors(&outline, point) >= 2, "elipse {rx}x{ry} a {angle}: {point:?} está aislado");
+            }
+
+            let mut fill = Vec::new();
+            let axes = [(rx * cos, -rx * sin), (ry * sin, ry * cos)];
+            write_affine_ellipse_fill((0, 0), axes, &mut fill);
+            // Los extremos de cada fila se incluyen, por lo que el relleno cubre un poco más que el área
+            let area = PI * rx * ry;
+            assert!((fill.len() as f32 - area).abs() < 2.0 * (rx + ry), "elipse {rx}x{ry}: área {} != {area}", fill.len());
+        }
+
+        // Con ejes paralelos la elipse es un segmento
+        let mut segment = Vec::new();
+        write_affine_ellipse((0, 0), [(3.0, 4.0), (6.0, 8.0)], &mut segment);
+        assert_eq!((segment.first(), segment.last()), (Some(&(-7, -9)), Some(&(7, 9))));
+        let mut fill = Vec::new();
+        write_affine_ellipse_fill((0, 0), [(3.0, 4.0), (6.0, 8.0)], &mut fill);
+        assert!(fill.is_empty());
+    }
+
+    #[track_caller]
+    fn compare(mut a_points: Vec<PixelCoord>, mut b_points: Vec<PixelCoord>) {
+        let (b, a) = (b_points.last().unwrap(), b_points.first().unwrap());
+        let (x, y) = (b.0 - a.0, b.1 - a.1);
+        tracing::debug!(
+            "Componentes: x:{x:?} y:{y:?}. Angle: {alpha:.2} deg",
+            alpha = f64::atan(y as f64 / x as f64).to_degrees()
+        );
+        a_points.sort();
+        b_points.sort();
+
+        assert_eq!(a_points.len(), b_points.len(), "Los dos arreglos no tienen la misma longitud");
+        let longest = a_points.len().max(b_points.len());
+        let mut errors = 0;
+        let mut report = Vec::new();
+        for idx in 0..longest {
+            let i = a_points.get(idx);
+            let j = b_points.get(idx);
+            if i != j {
+                report.push(Err(format!("Se esperaba que los puntos fueran iguales: {:?} != {:?}", i, j)));
+                errors += 1;
+            } else {
+                report.push(Ok(format!("Los puntos son iguales: {:?} ", j)));
+            }
+        }
+        if errors > longest * 5 / 100 {
+            for r in report.into_iter() {
+                match r {
+                    Ok(ok) => tracing::info!("{}", ok),
+                    Err(err) => tracing::error!("{}", err),
+                }
+            }
+            panic!("Los puntos no son iguales");
+        }
     }
 }
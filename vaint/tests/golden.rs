@@ -0,0 +1,327 @@
+//! Pruebas de regresión basadas en imágenes de referencia ("golden images").
+//!
+//! Cada caso se renderiza a una imagen y se compara pixel a pixel con su referencia en
+//! `tests/golden/<nombre>.png`. Si la imagen difiere, se escribe la imagen obtenida y una imagen de
+//! diferencias (pixeles distintos en rojo) en el directorio temporal de pruebas de cargo.
+//!
+//! Para regenerar las referencias tras un cambio intencional:
+//!
+//! ```bash
+//! VAINT_BLESS=1 cargo test --test golden
+//! ```
+use std::path::PathBuf;
+
+use image::{Rgb, RgbImage};
+use vaint::algorithms::{CoveragePoint, FillRule, LineAlgorithm, LineJoin};
+use vaint::geometries::Transform;
+use vaint::{Color, PixelCoord, ShapeObject, algorithms};
+
+const WHITE: Color = Color::from_u32_rgb(0xFFFFFF);
+const RED: Color = Color::from_u32_rgb(0xFF0000);
+const BLUE: Color = Color::from_u32_rgb(0x0000FF);
+const DIMENSIONS: (u32, u32) = (64, 64);
+const CENTER: PixelCoord = (32, 32);
+
+/// Compara la imagen dada contra la referencia `tests/golden/<name>.png`.
+#[track_caller]
+fn assert_golden(name: &str, actual: &RgbImage) {
+    let reference_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("{name}.png"));
+
+    if std::env::var_os("VAINT_BLESS").is_some() {
+        std::fs::create_dir_all(reference_path.parent().unwrap()).unwrap();
+        actual.save(&reference_path).unwrap();
+        return;
+    }
+
+    let Ok(expected) = image::open(&reference_path) else {
+        panic!("No existe la referencia `{}`. Ejecute con `VAINT_BLESS=1` para crearla.", reference_path.display());
+    };
+    let expected = expected.to_rgb8();
+
+    assert_eq!(expected.dimensions(), actual.dimensions(), "`{name}`: Las dimensiones de las imágenes no coinciden");
+    let mismatches = expected.pixels().zip(actual.pixels()).filter(|(e, a)| e != a).count();
+    if mismatches == 0 {
+        return;
+    }
+
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let actual_path = out_dir.join(format!("{name}.actual.png"));
+    let diff_path = out_dir.join(format!("{name}.diff.png"));
+    actual.save(&actual_path).unwrap();
+    diff_image(&expected, actual).save(&diff_path).unwrap();
+
+    panic!(
+        "`{name}`: {mismatches} pixeles difieren de la referencia.\nObtenido: {}\nDiferencias: {}",
+        actual_path.display(),
+        diff_path.display()
+    );
+}
+
+/// Genera una imagen con los pixeles distintos en rojo sobre una versión atenuada de la referencia.
+fn diff_image(expected: &RgbImage, actual: &RgbImage) -> RgbImage {
+    RgbImage::from_fn(expected.width(), expected.height(), |x, y| {
+        let (e, a) = (expected.get_pixel(x, y), actual.get_pixel(x, y));
+        if e != a {
+            return Rgb([255, 0, 0]);
+        }
+        let luma = (e.0.iter().map(|&c| c as u32).sum::<u32>() / 3) as u8;
+        let faded = 192 + luma / 4;
+        Rgb([faded, faded, faded])
+    })
+}
+
+/// Dibuja los puntos dados en negro sobre un lienzo blanco.
+fn plot(points: &[PixelCoord]) -> RgbImage {
+    let mut image = RgbImage::from_pixel(DIMENSIONS.0, DIMENSIONS.1, WHITE.into());
+    for &(x, y) in points {
+        if (0..DIMENSIONS.0 as i32).contains(&x) && (0..DIMENSIONS.1 as i32).contains(&y) {
+            image.put_pixel(x as u32, y as u32, Color::BLACK.into());
+        }
+    }
+    image
+}
+
+/// Dibuja los puntos dados en negro sobre un lienzo blanco, atenuados según su cobertura.
+fn plot_coverage(points: &[CoveragePoint]) -> RgbImage {
+    let mut image = RgbImage::from_pixel(DIMENSIONS.0, DIMENSIONS.1, WHITE.into());
+    for &((x, y), coverage) in points {
+        if (0..DIMENSIONS.0 as i32).contains(&x) && (0..DIMENSIONS.1 as i32).contains(&y) {
+            let luma = (255.0 * (1.0 - coverage)).round() as u8;
+            image.put_pixel(x as u32, y as u32, Rgb([luma, luma, luma]));
+        }
+    }
+    image
+}
+
+/// Renderiza una sola figura sobre un lienzo blanco.
+fn render(shape: ShapeObject) -> RgbImage { vaint::raster::render(&vec![shape], WHITE, DIMENSIONS) }
+
+/// Renderiza una figura con borde rojo de grosor 3 y relleno azul.
+fn render_filled(mut shape: ShapeObject) -> RgbImage {
+    *shape.style_mut() = shape.style_mut().stroke_color(RED).fill_color(BLUE).stroke_width(3.0);
+    render(shape)
+}
+
+#[test]
+fn algorithm_dda() {
+    let mut points = Vec::new();
+    for end in [(60, 32), (60, 50), (50, 60), (32, 60), (10, 55), (4, 32), (10, 4), (40, 4)] {
+        algorithms::write_dda(CENTER, end, &mut points);
+    }
+    assert_golden("algorithm_dda", &plot(&points));
+}
+
+#[test]
+fn algorithm_bresenham() {
+    let mut points = Vec::new();
+    for end in [(60, 32), (60, 50), (50, 60), (32, 60), (10, 55), (4, 32), (10, 4), (40, 4)] {
+        algorithms::write_bresenham(CENTER, end, &mut points);
+    }
+    assert_golden("algorithm_bresenham", &plot(&points));
+}
+
+#[test]
+fn algorithm_circle_middle_point() {
+    let mut points = Vec::new();
+    for radius in [0, 1, 5, 12, 29] {
+        algorithms::write_circle_middle_point(CENTER, radius, &mut points);
+    }
+    assert_golden("algorithm_circle_middle_point", &plot(&points));
+}
+
+#[test]
+fn algorithm_ellipse_middle_point() {
+    let mut points = Vec::new();
+    algorithms::write_ellipse_middle_point(CENTER, 29, 12, &mut points);
+    algorithms::write_ellipse_middle_point(CENTER, 8, 25, &mut points);
+    algorithms::write_ellipse_middle_point(CENTER, 3, 1, &mut points);
+    assert_golden("algorithm_ellipse_middle_point", &plot(&points));
+}
+
+#[test]
+fn algorithm_wu_line() {
+    let mut points = Vec::new();
+    for end in [(60, 32), (60, 50), (50, 60), (32, 60), (10, 55), (4, 32), (10, 4), (40, 4)] {
+        algorithms::write_wu_line(CENTER, end, &mut points);
+    }
+    assert_golden("algorithm_wu_line", &plot_coverage(&points));
+}
+
+#[test]
+fn algorithm_wu_circle() {
+    let mut points = Vec::new();
+    for radius in [0, 1, 5, 12, 29] {
+        algorithms::write_wu_circle(CENTER, radius, &mut points);
+    }
+    assert_golden("algorithm_wu_circle", &plot_coverage(&points));
+}
+
+#[test]
+fn algorithm_wu_ellipse() {
+    let mut points = Vec::new();
+    algorithms::write_wu_ellipse(CENTER, 29, 12, &mut points);
+    algorithms::write_wu_ellipse(CENTER, 8, 25, &mut points);
+    algorithms::write_wu_ellipse(CENTER, 3, 1, &mut points);
+    assert_golden("algorithm_wu_ellipse", &plot_coverage(&points));
+}
+
+#[test]
+fn algorithm_flood_fill() {
+    let mut outline = Vec::new();
+    algorithms::write_circle_middle_point(CENTER, 20, &mut outline);
+    let mut fill = Vec::new();
+    algorithms::flood_fill(&outline, &mut fill);
+    assert_golden("algorithm_flood_fill", &plot(&fill));
+}
+
+#[test]
+fn shape_circle() { assert_golden("shape_circle", &render(ShapeObject::new_circle(25, CENTER))); }
+
+#[test]
+fn shape_ellipse() { assert_golden("shape_ellipse", &render(ShapeObject::new_ellipse(28, 14, CENTER))); }
+
+#[test]
+fn shape_square() { assert_golden("shape_square", &render(ShapeObject::new_square(40, CENTER))); }
+
+#[test]
+fn shape_rectangle() { assert_golden("shape_rectangle", &render(ShapeObject::new_rectangle(50, 21, CENTER))); }
+
+#[test]
+fn shape_line() {
+    let dda = ShapeObject::new_line((4, 10), (60, 30), LineAlgorithm::Dda);
+    let bresenham = ShapeObject::new_line((4, 30), (60, 50), LineAlgorithm::Bresenham);
+    assert_golden("shape_line", &vaint::raster::render(&vec![dda, bresenham], WHITE, DIMENSIONS));
+}
+
+#[test]
+fn shape_polyline() {
+    let points = [(4, 60), (16, 8), (32, 50), (48, 8), (60, 60)];
+    assert_golden("shape_polyline", &render(ShapeObject::new_polyline(&points, LineAlgorithm::Bresenham)));
+}
+
+#[test]
+fn shape_bezier() {
+    let quadratic = ShapeObject::new_bezier(&[(4, 28), (32, -20), (60, 28)], LineAlgorithm::Bresenham);
+    let cubic = ShapeObject::new_bezier(&[(4, 60), (20, 20), (44, 68), (60, 32)], LineAlgorithm::Bresenham);
+    assert_golden("shape_bezier", &vaint::raster::render(&vec![quadratic, cubic], WHITE, DIMENSIONS));
+}
+
+#[test]
+fn shape_pie_chart() {
+    use std::f32::consts::TAU;
+
+    let slices = [(0.0, 0.5, RED), (0.5, 0.8, BLUE), (0.8, 1.0, Color::from_u32_rgb(0x00FF00))];
+    let shapes = slices
+        .into_iter()
+        .map(|(start, end, color)| {
+            let mut pie = ShapeObject::new_pie(28, 28, start * TAU, end * TAU, CENTER);
+            *pie.style_mut() = pie.style_mut().stroke_color(Color::BLACK).fill_color(color);
+            pie
+        })
+        .collect::<Vec<_>>();
+    assert_golden("shape_pie_chart", &vaint::raster::render(&shapes, WHITE, DIMENSIONS));
+}
+
+#[test]
+fn shape_arcs() {
+    use std::f32::consts::PI;
+
+    let arc = ShapeObject::new_arc(28, 14, PI / 4.0, 7.0 * PI / 4.0, (32, 16));
+    let mut chord = ShapeObject::new_chord(28, 14, -PI / 6.0, 7.0 * PI / 6.0, (32, 44));
+    *chord.style_mut() = chord.style_mut().stroke_color(RED).fill_color(BLUE);
+    assert_golden("shape_arcs", &vaint::raster::render(&vec![arc, chord], WHITE, DIMENSIONS));
+}
+
+#[test]
+fn shape_anti_aliased() {
+    let mut shapes = vec![
+        ShapeObject::new_circle(25, CENTER),
+        ShapeObject::new_line((4, 10), (60, 30), LineAlgorithm::Bresenham),
+        ShapeObject::new_polygon(&[(32, 12), (52, 48), (12, 48)], FillRule::EvenOdd),
+    ];
+    for shape in shapes.iter_mut() {
+        *shape.style_mut() = shape.style_mut().stroke_color(RED).fill_color(BLUE).anti_aliasing(true);
+    }
+    assert_golden("shape_anti_aliased", &vaint::raster::render(&shapes, WHITE, DIMENSIONS));
+}
+
+#[test]
+fn stroke_joins() {
+    let zigzag = [(-24, 8), (-12, -8), (0, 8), (12, -8), (24, 8)];
+    let shapes = [LineJoin::Miter, LineJoin::Round, LineJoin::Bevel]
+        .into_iter()
+        .enumerate()
+        .map(|(i, join)| {
+            let points = zigzag.map(|(x, y)| (CENTER.0 + x, 12 + 20 * i as i32 + y));
+            let mut shape = ShapeObject::new_polyline(&points, LineAlgorithm::Bresenham);
+            *shape.style_mut() = shape.style_mut().stroke_color(RED).stroke_width(5.0).line_join(join);
+            shape
+        })
+        .collect::<Vec<_>>();
+    assert_golden("stroke_joins", &vaint::raster::render(&shapes, WHITE, DIMENSIONS));
+}
+
+#[test]
+fn clipped_shape() {
+    let region = algorithms::ClipRegion::new(vec![(CENTER.0, 2), (62, 50), (2, 50)]).unwrap();
+    let mut shape = ShapeObject::new_circle(25, CENTER).clip_region(region);
+    *shape.style_mut() = shape.style_mut().stroke_color(RED).fill_color(BLUE);
+    assert_golden("clipped_shape", &render(shape));
+}
+
+#[test]
+fn filled_rounded_rect() {
+    assert_golden("filled_rounded_rect", &render_filled(ShapeObject::new_rounded_rect(52, 36, 12, CENTER)));
+}
+
+#[test]
+fn filled_regular_polygon() {
+    let hexagon = ShapeObject::new_regular_polygon(6, 28, CENTER, std::f32::consts::FRAC_PI_6);
+    assert_golden("filled_regular_polygon", &render_filled(hexagon));
+}
+
+#[test]
+fn filled_star() { assert_golden("filled_star", &render_filled(ShapeObject::new_star(5, 30, 12, CENTER))); }
+
+#[test]
+fn transformed_ellipse() {
+    let ellipse = ShapeObject::new_ellipse(28, 12, CENTER).transform(Transform::rotate(std::f32::consts::FRAC_PI_6));
+    assert_golden("transformed_ellipse", &render_filled(ellipse));
+}
+
+#[test]
+fn transformed_rectangle() {
+    let transform = Transform::shear(0.5, 0.0).then(Transform::rotate(0.4)).then(Transform::translate(-4.0, 2.0));
+    assert_golden("transformed_rectangle", &render_filled(ShapeObject::new_rectangle(30, 20, CENTER).transform(transform)));
+}
+
+#[test]
+fn filled_polygon() {
+    let u_shape = [(8, 8), (24, 8), (24, 40), (40, 40), (40, 8), (56, 8), (56, 56), (8, 56)];
+    assert_golden("filled_polygon", &render_filled(ShapeObject::new_polygon(&u_shape, FillRule::EvenOdd)));
+}
+
+#[test]
+fn filled_polygon_fill_rules() {
+    let pentagram = [(32, 4), (49, 58), (4, 24), (60, 24), (15, 58)];
+    let mut even_odd = ShapeObject::new_polygon(&pentagram, FillRule::EvenOdd);
+    *even_odd.style_mut() = even_odd.style_mut().fill_color(BLUE);
+    let mut non_zero = ShapeObject::new_polygon(&pentagram, FillRule::NonZero);
+    non_zero.center.1 += 64;
+    *non_zero.style_mut() = non_zero.style_mut().fill_color(BLUE);
+    let image = vaint::raster::render(&vec![even_odd, non_zero], WHITE, (DIMENSIONS.0, DIMENSIONS.1 * 2));
+    assert_golden("filled_polygon_fill_rules", &image);
+}
+
+#[test]
+fn filled_circle() { assert_golden("filled_circle", &render_filled(ShapeObject::new_circle(25, CENTER))); }
+
+#[test]
+fn filled_ellipse() { assert_golden("filled_ellipse", &render_filled(ShapeObject::new_ellipse(14, 28, CENTER))); }
+
+#[test]
+fn filled_square() { assert_golden("filled_square", &render_filled(ShapeObject::new_square(40, CENTER))); }
+
+#[test]
+fn filled_rectangle() { assert_golden("filled_rectangle", &render_filled(ShapeObject::new_rectangle(50, 21, CENTER))); }